@@ -46,8 +46,21 @@ fn detect_track_caller() {
     }
 }
 
+#[cfg(not(any(feature = "error", feature = "try_into")))]
+fn detect_core_error() {}
+/// Detect availability of `core::error::Error`, so derived `Error`
+/// implementations can be used in `no_std` crates.
+#[cfg(any(feature = "error", feature = "try_into"))]
+fn detect_core_error() {
+    use rustc_version::version_meta;
+    if version_meta().unwrap().semver.minor >= 81 {
+        println!("cargo:rustc-cfg=feature=\"core-error\"");
+    }
+}
+
 fn main() {
     detect_nightly();
     detect_track_caller();
+    detect_core_error();
     generate_peg();
 }
@@ -0,0 +1,86 @@
+use crate::utils::{AttrParams, DeriveType, State};
+use convert_case::{Case, Casing};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{DeriveInput, Fields, Ident, Result};
+
+pub fn expand(input: &DeriveInput, trait_name: &'static str) -> Result<TokenStream> {
+    let state = State::with_attr_params(
+        input,
+        trait_name,
+        quote!(),
+        String::from("as_variant"),
+        AttrParams {
+            enum_: vec!["ignore", "vis"],
+            variant: vec!["ignore"],
+            struct_: vec!["ignore"],
+            field: vec!["ignore"],
+        },
+    )?;
+    assert!(
+        state.derive_type == DeriveType::Enum,
+        "AsVariant can only be derived for enums"
+    );
+
+    let enum_name = &input.ident;
+    let (imp_generics, type_generics, where_clause) = input.generics.split_for_impl();
+
+    let vis = state.default_info.info.method_vis();
+
+    let mut funcs = vec![];
+    for variant_state in state.enabled_variant_data().variant_states {
+        let variant = variant_state.variant.unwrap();
+        let fn_name = Ident::new(
+            &format_ident!("as_{}", variant.ident)
+                .to_string()
+                .to_case(Case::Snake),
+            variant.ident.span(),
+        );
+        let fn_name_mut = format_ident!("{}_mut", fn_name);
+        let variant_ident = &variant.ident;
+
+        let vars: Vec<_> = (0..variant.fields.len())
+            .map(|n| format_ident!("field_{}", n))
+            .collect();
+        let data_pattern = match variant.fields {
+            Fields::Named(ref fields) => {
+                let field_names = fields.named.iter().map(|f| &f.ident);
+                quote! { { #(#field_names: #vars),* } }
+            }
+            Fields::Unnamed(_) => quote! { (#(#vars),*) },
+            Fields::Unit => quote! {},
+        };
+        let field_types: Vec<_> = variant.fields.iter().map(|f| &f.ty).collect();
+
+        let func = quote! {
+            #vis fn #fn_name(&self) -> ::core::option::Option<(#(&#field_types),*)> {
+                match self {
+                    #enum_name ::#variant_ident #data_pattern => {
+                        ::core::option::Option::Some((#(#vars),*))
+                    }
+                    _ => ::core::option::Option::None
+                }
+            }
+
+            #vis fn #fn_name_mut(
+                &mut self,
+            ) -> ::core::option::Option<(#(&mut #field_types),*)> {
+                match self {
+                    #enum_name ::#variant_ident #data_pattern => {
+                        ::core::option::Option::Some((#(#vars),*))
+                    }
+                    _ => ::core::option::Option::None
+                }
+            }
+        };
+        funcs.push(func);
+    }
+
+    let imp = quote! {
+        impl #imp_generics #enum_name #type_generics #where_clause{
+            #(#funcs)*
+        }
+    };
+
+    Ok(imp)
+}
@@ -1,49 +1,129 @@
 use crate::utils::{
-    field_idents, get_field_types, named_to_vec, numbered_vars, unnamed_to_vec,
+    method_ident, numbered_vars, variant_method_names, AttrParams, DeriveType,
+    MultiFieldData, State,
 };
 use proc_macro2::TokenStream;
 use quote::quote;
-use syn::{Data, DeriveInput, Field, Fields, Ident};
+use syn::{spanned::Spanned as _, DeriveInput, Error, Result, Type};
 
 /// Provides the hook to expand `#[derive(Constructor)]` into an implementation of `Constructor`
-pub fn expand(input: &DeriveInput, _: &str) -> TokenStream {
+pub fn expand(input: &DeriveInput, trait_name: &'static str) -> Result<TokenStream> {
+    let state = State::with_attr_params(
+        input,
+        trait_name,
+        quote!(),
+        String::from("constructor"),
+        AttrParams {
+            enum_: vec!["rename", "vis"],
+            variant: vec!["ignore", "rename"],
+            struct_: vec!["vis"],
+            field: vec![],
+        },
+    )?;
+    let vis = state.default_info.info.method_vis();
+
+    // Fields are configured with a separate `#[new(...)]` attribute.
+    let fields_state = State::with_type_bound(
+        input,
+        trait_name,
+        quote!(),
+        String::from("new"),
+        AttrParams {
+            enum_: vec![],
+            variant: vec![],
+            struct_: vec![],
+            field: vec!["default", "value"],
+        },
+        false,
+    )?;
+
     let input_type = &input.ident;
     let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
-    let ((body, vars), fields) = match input.data {
-        Data::Struct(ref data_struct) => match data_struct.fields {
-            Fields::Unnamed(ref fields) => {
-                let field_vec = unnamed_to_vec(fields);
-                (tuple_body(input_type, &field_vec), field_vec)
-            }
-            Fields::Named(ref fields) => {
-                let field_vec = named_to_vec(fields);
-                (struct_body(input_type, &field_vec), field_vec)
-            }
-            Fields::Unit => (struct_body(input_type, &[]), vec![]),
-        },
-        _ => panic!("Only structs can derive a constructor"),
-    };
-    let original_types = &get_field_types(&fields);
-    quote! {
-        #[allow(missing_docs)]
-        impl#impl_generics #input_type#ty_generics #where_clause {
+
+    let mut funcs = vec![];
+    if state.derive_type == DeriveType::Enum {
+        let fn_names = variant_method_names(&state, "new_{}")?;
+        let variant_states = state
+            .variant_states
+            .iter()
+            .zip(&fields_state.variant_states)
+            .filter(|(variant_state, _)| variant_state.default_info.enabled)
+            .map(|(_, fields_state)| fields_state);
+        for (fields_state, fn_name) in variant_states.zip(fn_names) {
+            let variant = fields_state.variant.unwrap();
+            let fn_name = method_ident(&fn_name, variant.ident.span())?;
+            let multi_field_data = fields_state.enabled_fields_data();
+            let (args, initializers) = args_and_initializers(&multi_field_data)?;
+            let body = multi_field_data.initializer(&initializers);
+            funcs.push(quote! {
+                #[inline]
+                #vis fn #fn_name(#(#args),*) -> #input_type#ty_generics {
+                    #body
+                }
+            });
+        }
+    } else {
+        let multi_field_data = fields_state.enabled_fields_data();
+        let (args, initializers) = args_and_initializers(&multi_field_data)?;
+        let body = multi_field_data.initializer(&initializers);
+        funcs.push(quote! {
             #[inline]
-            pub fn new(#(#vars: #original_types),*) -> #input_type#ty_generics {
+            #vis fn new(#(#args),*) -> #input_type#ty_generics {
                 #body
             }
-        }
+        });
     }
+
+    Ok(quote! {
+        #[allow(missing_docs)]
+        impl#impl_generics #input_type#ty_generics #where_clause {
+            #(#funcs)*
+        }
+    })
 }
 
-fn tuple_body(return_type: &Ident, fields: &[&Field]) -> (TokenStream, Vec<Ident>) {
-    let vars = &numbered_vars(fields.len(), "");
-    (quote!(#return_type(#(#vars),*)), vars.clone())
+/// Returns the arguments of the `new` method and the expressions initializing
+/// each field.
+///
+/// Fields with a `#[new(default)]` or `#[new(value = "...")]` attribute and
+/// `PhantomData` fields don't become arguments.
+fn args_and_initializers(
+    data: &MultiFieldData,
+) -> Result<(Vec<TokenStream>, Vec<TokenStream>)> {
+    let vars = numbered_vars(data.fields.len(), "");
+    let mut args = vec![];
+    let mut initializers = vec![];
+    for (i, field) in data.fields.iter().enumerate() {
+        let info = &data.infos[i].info;
+        let initializer = match (info.default, &info.value) {
+            (Some(true), Some(_)) => {
+                return Err(Error::new(
+                    field.span(),
+                    "`default` and `value` can't be used together",
+                ))
+            }
+            (Some(true), None) => quote!(::core::default::Default::default()),
+            (_, Some(value)) => value.clone(),
+            _ if is_phantom_data(&field.ty) => quote!(::core::marker::PhantomData),
+            _ => {
+                let var = field.ident.as_ref().unwrap_or(&vars[i]);
+                let ty = &field.ty;
+                args.push(quote!(#var: #ty));
+                quote!(#var)
+            }
+        };
+        initializers.push(initializer);
+    }
+    Ok((args, initializers))
 }
 
-fn struct_body(return_type: &Ident, fields: &[&Field]) -> (TokenStream, Vec<Ident>) {
-    let field_names: &Vec<Ident> =
-        &field_idents(fields).iter().map(|f| (**f).clone()).collect();
-    let vars = field_names;
-    let ret_vars = field_names.clone();
-    (quote!(#return_type{#(#field_names: #vars),*}), ret_vars)
+fn is_phantom_data(ty: &Type) -> bool {
+    match ty {
+        Type::Path(ty) => ty
+            .path
+            .segments
+            .last()
+            .map_or(false, |segment| segment.ident == "PhantomData"),
+        _ => false,
+    }
 }
@@ -0,0 +1,270 @@
+use crate::display::{
+    trait_name_to_trait_bound, validate_placeholders, Parameter, Placeholder,
+};
+use crate::utils::{
+    add_extra_where_clauses, get_if_type_parameter_used_in_type, numbered_vars,
+    HashMap, HashSet,
+};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{spanned::Spanned as _, DeriveInput, Error, Fields, Ident, Result};
+
+/// Provides the hook to expand `#[derive(Debug)]` into an implementation of `Debug`
+pub fn expand(input: &DeriveInput, _: &'static str) -> Result<TokenStream> {
+    if let Some(attr) = input.attrs.iter().find(|attr| attr.path.is_ident("debug")) {
+        return Err(Error::new(
+            attr.span(),
+            "`#[debug(...)]` attributes are only supported on fields",
+        ));
+    }
+
+    let type_params: HashSet<_> = input
+        .generics
+        .type_params()
+        .map(|t| t.ident.clone())
+        .collect();
+    let name = &input.ident;
+
+    let mut bounds = HashMap::default();
+    let arms = match &input.data {
+        syn::Data::Struct(data) => {
+            let (pattern, body) = expand_fields(
+                &data.fields,
+                name,
+                is_packed(input)?,
+                &type_params,
+                &mut bounds,
+            )?;
+            quote!(#name #pattern => #body,)
+        }
+        syn::Data::Enum(data) => {
+            let mut arms = TokenStream::new();
+            for variant in &data.variants {
+                if let Some(attr) = variant
+                    .attrs
+                    .iter()
+                    .find(|attr| attr.path.is_ident("debug"))
+                {
+                    return Err(Error::new(
+                        attr.span(),
+                        "`#[debug(...)]` attributes are only supported on fields",
+                    ));
+                }
+                let variant_name = &variant.ident;
+                let (pattern, body) = expand_fields(
+                    &variant.fields,
+                    variant_name,
+                    false,
+                    &type_params,
+                    &mut bounds,
+                )?;
+                arms.extend(quote!(#name::#variant_name #pattern => #body,));
+            }
+            arms
+        }
+        syn::Data::Union(_) => {
+            return Err(Error::new(
+                input.span(),
+                "derive(Debug) is not supported for unions",
+            ))
+        }
+    };
+
+    let generics = if bounds.is_empty() {
+        input.generics.clone()
+    } else {
+        let bounds = bounds.into_iter().map(|(ty, bounds)| {
+            let bounds = bounds.into_iter();
+            quote!(#ty: #(#bounds)+*)
+        });
+        add_extra_where_clauses(&input.generics, quote!(where #(#bounds),*))
+    };
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        impl#impl_generics ::core::fmt::Debug for #name#ty_generics #where_clause {
+            #[inline]
+            fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                match *self {
+                    #arms
+                }
+            }
+        }
+    })
+}
+
+/// Checks whether the type is marked with `#[repr(packed)]` or
+/// `#[repr(packed(N))]`.
+fn is_packed(input: &DeriveInput) -> Result<bool> {
+    for attr in input.attrs.iter().filter(|attr| attr.path.is_ident("repr")) {
+        if let syn::Meta::List(list) = attr.parse_meta()? {
+            for nested in &list.nested {
+                let path = match nested {
+                    syn::NestedMeta::Meta(syn::Meta::Path(path)) => path,
+                    syn::NestedMeta::Meta(syn::Meta::List(list)) => &list.path,
+                    _ => continue,
+                };
+                if path.is_ident("packed") {
+                    return Ok(true);
+                }
+            }
+        }
+    }
+    Ok(false)
+}
+
+/// How a field is formatted, as specified by its `#[debug(...)]` attribute.
+enum FieldFmt {
+    /// The `Debug` implementation of the field is used.
+    Debug,
+    /// The field is left out of the output: `#[debug(skip)]`.
+    Skip,
+    /// The field is formatted with a format string: `#[debug(fmt = "...")]` or
+    /// `#[debug("...")]`. If the format string contains no placeholders, it's
+    /// written instead of the field.
+    Fmt(syn::LitStr),
+}
+
+fn parse_field_fmt(field: &syn::Field) -> Result<FieldFmt> {
+    let mut attrs = field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("debug"));
+    let attr = match attrs.next() {
+        Some(attr) => attr,
+        None => return Ok(FieldFmt::Debug),
+    };
+    if let Some(attr) = attrs.next() {
+        return Err(Error::new(attr.span(), "Too many attributes specified"));
+    }
+
+    let proper_syntax = || {
+        Error::new(
+            attr.span(),
+            r#"Proper syntax: #[debug(skip)], #[debug(fmt = "...")] or #[debug("...")]"#,
+        )
+    };
+    let list = match attr.parse_meta()? {
+        syn::Meta::List(list) if list.nested.len() == 1 => list,
+        _ => return Err(proper_syntax()),
+    };
+    match &list.nested[0] {
+        syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("skip") => {
+            Ok(FieldFmt::Skip)
+        }
+        syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+            path,
+            lit: syn::Lit::Str(fmt),
+            ..
+        })) if path.is_ident("fmt") => Ok(FieldFmt::Fmt(fmt.clone())),
+        syn::NestedMeta::Lit(syn::Lit::Str(fmt)) => Ok(FieldFmt::Fmt(fmt.clone())),
+        _ => Err(proper_syntax()),
+    }
+}
+
+/// Returns the pattern destructuring the given fields and the expression
+/// formatting them with `debug_struct` or `debug_tuple`.
+///
+/// The fields of a `packed` struct are copied instead of being borrowed, as
+/// references to them may be unaligned, so they have to be `Copy`, like for the
+/// `Debug` derive of the standard library.
+///
+/// The trait bounds required for fields with a generic type are added to
+/// `bounds`.
+fn expand_fields(
+    fields: &Fields,
+    name: &Ident,
+    packed: bool,
+    type_params: &HashSet<Ident>,
+    bounds: &mut HashMap<syn::Type, HashSet<syn::TraitBound>>,
+) -> Result<(TokenStream, TokenStream)> {
+    let name_str = unraw(name);
+    let vars = numbered_vars(fields.len(), "");
+    let mut patterns = vec![];
+    let mut calls = vec![];
+    for (field, var) in fields.iter().zip(&vars) {
+        let value = match parse_field_fmt(field)? {
+            FieldFmt::Skip => {
+                patterns.push(quote!(_));
+                continue;
+            }
+            FieldFmt::Debug => {
+                if let Some(ty) =
+                    get_if_type_parameter_used_in_type(type_params, &field.ty)
+                {
+                    bounds
+                        .entry(ty)
+                        .or_default()
+                        .insert(trait_name_to_trait_bound("Debug"));
+                }
+                if packed {
+                    quote!(&#var)
+                } else {
+                    quote!(#var)
+                }
+            }
+            FieldFmt::Fmt(fmt) => {
+                validate_placeholders(&fmt)?;
+                let placeholders = Placeholder::parse_fmt_string(&fmt.value());
+                if placeholders.iter().any(|p| {
+                    p.arg != Parameter::Positional(0)
+                        || p.width.is_some()
+                        || p.precision.is_some()
+                }) {
+                    return Err(Error::new(
+                        fmt.span(),
+                        "Placeholders in the format of a field can only refer to \
+                         the field itself",
+                    ));
+                }
+                if placeholders.is_empty() {
+                    quote!(&format_args!(#fmt))
+                } else {
+                    if let Some(ty) =
+                        get_if_type_parameter_used_in_type(type_params, &field.ty)
+                    {
+                        let ty_bounds = bounds.entry(ty).or_default();
+                        for placeholder in placeholders {
+                            ty_bounds.insert(trait_name_to_trait_bound(
+                                placeholder.trait_name,
+                            ));
+                        }
+                    }
+                    quote!(&format_args!(#fmt, #var))
+                }
+            }
+        };
+        patterns.push(if packed {
+            quote!(#var)
+        } else {
+            quote!(ref #var)
+        });
+        calls.push(match &field.ident {
+            Some(ident) => {
+                let ident_str = unraw(ident);
+                quote!(.field(#ident_str, #value))
+            }
+            None => quote!(.field(#value)),
+        });
+    }
+
+    Ok(match fields {
+        Fields::Named(_) => {
+            let idents = fields.iter().map(|field| &field.ident);
+            (
+                quote!({ #(#idents: #patterns),* }),
+                quote!(f.debug_struct(#name_str) #(#calls)* .finish()),
+            )
+        }
+        Fields::Unnamed(_) => (
+            quote!(( #(#patterns),* )),
+            quote!(f.debug_tuple(#name_str) #(#calls)* .finish()),
+        ),
+        Fields::Unit => (quote!(), quote!(f.write_str(#name_str))),
+    })
+}
+
+/// Returns the name of an identifier without the `r#` prefix of raw identifiers.
+fn unraw(ident: &Ident) -> String {
+    ident.to_string().trim_start_matches("r#").to_owned()
+}
@@ -1,12 +1,13 @@
-use std::{fmt::Display, str::FromStr as _};
+use std::{cell::Cell, fmt::Display, str::FromStr as _};
 
+use convert_case::{Case, Casing as _};
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::{quote, quote_spanned};
 use syn::{
     parse::Parser as _, punctuated::Punctuated, spanned::Spanned as _, Error, Result,
 };
 
-use crate::utils;
+use crate::{error::error_source_field, utils};
 use utils::{HashMap, HashSet};
 
 /// Provides the hook to expand `#[derive(Display)]` into an implementation of `From`
@@ -21,17 +22,20 @@ pub fn expand(input: &syn::DeriveInput, trait_name: &str) -> Result<TokenStream>
         .map(|t| t.ident.clone())
         .collect();
 
-    let ParseResult {
-        arms,
-        bounds,
-        requires_helper,
-    } = State {
+    let state = State {
         trait_path,
         trait_attr,
         input,
         type_params,
-    }
-    .get_match_arms_and_extra_bounds()?;
+        requires_pad_helper: Cell::new(false),
+        requires_builtins: Cell::new(false),
+        requires_display_as: Cell::new(false),
+    };
+    let ParseResult {
+        arms,
+        bounds,
+        requires_helper,
+    } = state.get_match_arms_and_extra_bounds()?;
 
     let generics = if !bounds.is_empty() {
         let bounds: Vec<_> = bounds
@@ -52,11 +56,21 @@ pub fn expand(input: &syn::DeriveInput, trait_name: &str) -> Result<TokenStream>
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
     let name = &input.ident;
 
-    let helper_struct = if requires_helper {
+    let helper_struct = if requires_helper || state.requires_display_as.get() {
         display_as_helper_struct()
     } else {
         TokenStream::new()
     };
+    let pad_helper = if state.requires_pad_helper.get() {
+        display_pad_helper()
+    } else {
+        TokenStream::new()
+    };
+    let builtins = if state.requires_builtins.get() {
+        state.builtin_bindings()?
+    } else {
+        TokenStream::new()
+    };
 
     Ok(quote! {
         impl #impl_generics #trait_path for #name #ty_generics #where_clause
@@ -65,6 +79,8 @@ pub fn expand(input: &syn::DeriveInput, trait_name: &str) -> Result<TokenStream>
             #[inline]
             fn fmt(&self, _derive_more_display_formatter: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
                 #helper_struct
+                #pad_helper
+                #builtins
 
                 match self {
                     #arms
@@ -104,7 +120,7 @@ fn attribute_name_to_trait_name(attribute_name: &str) -> &'static str {
     }
 }
 
-fn trait_name_to_trait_bound(trait_name: &str) -> syn::TraitBound {
+pub(crate) fn trait_name_to_trait_bound(trait_name: &str) -> syn::TraitBound {
     let path_segments_iterator = vec!["core", "fmt", trait_name]
         .into_iter()
         .map(|segment| syn::PathSegment::from(Ident::new(segment, Span::call_site())));
@@ -146,16 +162,136 @@ fn display_as_helper_struct() -> TokenStream {
         where
             F: ::core::ops::Fn(&mut ::core::fmt::Formatter) -> ::core::fmt::Result;
 
-        const _derive_more_DisplayAs_impl: () = {
-            impl<F> ::core::fmt::Display for _derive_more_DisplayAs<F>
-            where
-                F: ::core::ops::Fn(&mut ::core::fmt::Formatter) -> ::core::fmt::Result
-            {
+        impl<F> ::core::fmt::Display for _derive_more_DisplayAs<F>
+        where
+            F: ::core::ops::Fn(&mut ::core::fmt::Formatter) -> ::core::fmt::Result
+        {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                (self.0)(f)
+            }
+        }
+    }
+}
+
+/// Create a helper function that is required by variants formatted with the
+/// `pad` flag, e.g. `#[display(fmt = "<{}>", _0, pad)]`.
+///
+/// The function formats a variant without any formatting options and then
+/// applies the width, fill, alignment and precision of the outer formatter to
+/// the result as a whole, like `Formatter::pad` does for a `str`.
+///
+/// To avoid requiring `alloc`, the variant is formatted into a fixed-size
+/// buffer on the stack, which is then passed to `Formatter::pad`. If the
+/// output doesn't fit into the buffer, the variant is formatted twice instead:
+/// once to count its characters, and once more to write it out between the
+/// fill characters.
+fn display_pad_helper() -> TokenStream {
+    quote! {
+        fn _derive_more_pad(
+            f: &mut ::core::fmt::Formatter,
+            inner: &dyn ::core::ops::Fn(&mut ::core::fmt::Formatter) -> ::core::fmt::Result,
+        ) -> ::core::fmt::Result {
+            struct Inner<'a>(
+                &'a dyn ::core::ops::Fn(&mut ::core::fmt::Formatter) -> ::core::fmt::Result,
+            );
+            impl<'a> ::core::fmt::Display for Inner<'a> {
                 fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
                     (self.0)(f)
                 }
             }
-        };
+
+            struct Buffer {
+                bytes: [u8; 256],
+                len: usize,
+                overflowed: bool,
+            }
+            impl ::core::fmt::Write for Buffer {
+                fn write_str(&mut self, s: &str) -> ::core::fmt::Result {
+                    let end = self.len + s.len();
+                    if end > self.bytes.len() {
+                        self.overflowed = true;
+                        return ::core::result::Result::Err(::core::fmt::Error);
+                    }
+                    self.bytes[self.len..end].copy_from_slice(s.as_bytes());
+                    self.len = end;
+                    ::core::result::Result::Ok(())
+                }
+            }
+
+            struct Counter(usize);
+            impl ::core::fmt::Write for Counter {
+                fn write_str(&mut self, s: &str) -> ::core::fmt::Result {
+                    self.0 += s.chars().count();
+                    ::core::result::Result::Ok(())
+                }
+            }
+
+            struct Truncated<'a, 'b> {
+                f: &'a mut ::core::fmt::Formatter<'b>,
+                remaining: usize,
+            }
+            impl<'a, 'b> ::core::fmt::Write for Truncated<'a, 'b> {
+                fn write_str(&mut self, s: &str) -> ::core::fmt::Result {
+                    let end = s
+                        .char_indices()
+                        .nth(self.remaining)
+                        .map_or(s.len(), |(i, _)| i);
+                    self.remaining -= s[..end].chars().count();
+                    self.f.write_str(&s[..end])
+                }
+            }
+
+            let mut buffer = Buffer {
+                bytes: [0; 256],
+                len: 0,
+                overflowed: false,
+            };
+            match ::core::fmt::write(&mut buffer, format_args!("{}", Inner(inner))) {
+                ::core::result::Result::Ok(()) => {
+                    let s = ::core::str::from_utf8(&buffer.bytes[..buffer.len])
+                        .map_err(|_| ::core::fmt::Error)?;
+                    return f.pad(s);
+                }
+                ::core::result::Result::Err(e) if !buffer.overflowed => {
+                    return ::core::result::Result::Err(e);
+                }
+                ::core::result::Result::Err(_) => {}
+            }
+
+            let mut counter = Counter(0);
+            ::core::fmt::write(&mut counter, format_args!("{}", Inner(inner)))?;
+            let len = match f.precision() {
+                ::core::option::Option::Some(precision) if precision < counter.0 => {
+                    precision
+                }
+                _ => counter.0,
+            };
+            let padding = f.width().map_or(0, |width| width.saturating_sub(len));
+            let (pre, post) = match f.align() {
+                ::core::option::Option::Some(::core::fmt::Alignment::Right) => {
+                    (padding, 0)
+                }
+                ::core::option::Option::Some(::core::fmt::Alignment::Center) => {
+                    (padding / 2, (padding + 1) / 2)
+                }
+                _ => (0, padding),
+            };
+            let fill = f.fill();
+            for _ in 0..pre {
+                ::core::fmt::Write::write_char(f, fill)?;
+            }
+            ::core::fmt::write(
+                &mut Truncated {
+                    f: &mut *f,
+                    remaining: len,
+                },
+                format_args!("{}", Inner(inner)),
+            )?;
+            for _ in 0..post {
+                ::core::fmt::Write::write_char(f, fill)?;
+            }
+            ::core::result::Result::Ok(())
+        }
     }
 }
 
@@ -175,6 +311,16 @@ struct State<'a, 'b> {
     trait_attr: &'static str,
     input: &'a syn::DeriveInput,
     type_params: HashSet<Ident>,
+    /// Set to `true` once a format with the `pad` flag is parsed, meaning the
+    /// `_derive_more_pad` helper function is required.
+    requires_pad_helper: Cell<bool>,
+    /// Set to `true` once a format refers to one of the [`BUILTINS`], meaning
+    /// they have to be bound.
+    requires_builtins: Cell<bool>,
+    /// Set to `true` once a field with a `#[display(with = "...")]` attribute
+    /// is formatted, meaning the `_derive_more_DisplayAs` helper struct is
+    /// required.
+    requires_display_as: Cell<bool>,
 }
 
 impl<'a, 'b> State<'a, 'b> {
@@ -235,11 +381,17 @@ impl<'a, 'b> State<'a, 'b> {
             use syn::{Meta, NestedMeta};
             let meta_nv = match meta_list.nested.first() {
                 Some(NestedMeta::Meta(Meta::NameValue(meta_nv))) => meta_nv,
+                // `#[display("...")]` is a shorthand for `#[display(fmt = "...")]`.
+                Some(NestedMeta::Lit(syn::Lit::Str(_))) => {
+                    if meta_key == "fmt" {
+                        metas.push(meta);
+                    }
+                    continue;
+                }
                 _ => {
                     // If the given attribute is not MetaNameValue, it most likely implies that the
                     // user is writing an incorrect format. For example:
                     // - `#[display()]`
-                    // - `#[display("foo")]`
                     // - `#[display(foo)]`
                     return Err(Error::new(
                         meta.span(),
@@ -338,6 +490,8 @@ impl<'a, 'b> State<'a, 'b> {
     fn parse_meta_fmt(
         &self,
         meta: &syn::Meta,
+        fields: &syn::Fields,
+        variant: Option<usize>,
         outer_enum: bool,
     ) -> Result<(TokenStream, bool)> {
         let list = match meta {
@@ -347,108 +501,322 @@ impl<'a, 'b> State<'a, 'b> {
             }
         };
 
-        match &list.nested[0] {
-            syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
-                path,
-                lit: syn::Lit::Str(fmt),
-                ..
-            })) => match path {
-                op if op.segments.first().expect("path shouldn't be empty").ident
-                    == "fmt" =>
+        let fmt = match fmt_string(list) {
+            Some(fmt) => fmt,
+            None => {
+                return Err(Error::new(
+                    list.nested[0].span(),
+                    self.get_proper_fmt_syntax(),
+                ))
+            }
+        };
+
+        validate_placeholders(fmt)?;
+        let alt = alt_string(list)?;
+        if let Some(alt) = alt {
+            validate_placeholders(alt)?;
+        }
+        let pad = list.nested.iter().skip(1).find(|arg| is_flag(arg, "pad"));
+        let chain = list.nested.iter().skip(1).find(|arg| is_flag(arg, "chain"));
+
+        let expected_affix_usage = "outer `enum` `fmt` is an affix spec that expects no args and at most 1 placeholder for inner variant display";
+        if outer_enum {
+            if let Some(arg) = fmt_args(list).next() {
+                return Err(Error::new(arg.span(), expected_affix_usage));
+            }
+            if let Some(chain) = chain {
+                return Err(Error::new(
+                    chain.span(),
+                    "`chain` cannot be used on an outer `enum` `fmt`, use it on the variants instead",
+                ));
+            }
+            if let Some(alt) = alt {
+                return Err(Error::new(
+                    alt.span(),
+                    "`alt` cannot be used on an outer `enum` `fmt`, use it on the variants instead",
+                ));
+            }
+            // TODO: Check for a single `Display` group?
+            let num_placeholders = Placeholder::parse_fmt_string(&fmt.value())
+                .into_iter()
+                .filter(|pl| match pl.arg {
+                    Parameter::Positional(_) => true,
+                    Parameter::Named(_) => false,
+                })
+                .count();
+            if num_placeholders > 1 {
+                return Err(Error::new(list.nested[1].span(), expected_affix_usage));
+            }
+            if num_placeholders == 1 {
+                if let Some(pad) = pad {
+                    return Err(Error::new(
+                        pad.span(),
+                        "`pad` cannot be used on an outer `enum` `fmt` with a placeholder, use it on the variants instead",
+                    ));
+                }
+                let implicit_args =
+                    self.get_implicit_args(fmt, fields, &HashSet::default())?;
+                // The formatted variant is bound to `_derive_more_inner` by
+                // the caller.
+                return Ok((
+                    quote_spanned!(fmt.span()=> write!(
+                        _derive_more_display_formatter,
+                        #fmt,
+                        _derive_more_inner,
+                        #implicit_args
+                    )),
+                    true,
+                ));
+            }
+        }
+        let write = self.get_write(list, fmt, fmt_args(list), fields)?;
+        let write = match alt {
+            Some(alt) => {
+                let alt_write = self.get_write(list, alt, alt_args(list), fields)?;
+                // Checked before `pad` replaces the `Formatter`.
+                quote_spanned!(meta.span()=> if _derive_more_alternate {
+                    #alt_write
+                } else {
+                    #write
+                })
+            }
+            None => write,
+        };
+        let write = match chain {
+            Some(chain) => {
+                let index = error_source_field(self.input, variant)?.ok_or_else(|| {
+                    Error::new(
+                        chain.span(),
+                        "`chain` requires a source field, either named `source` or marked with `#[error(source)]`",
+                    )
+                })?;
+                let source = field_binding(fields, index);
+                let field = fields.iter().nth(index).unwrap();
+                // An optional source is only appended if there is one.
+                let write_source = if utils::get_single_type_argument(
+                    &field.ty,
+                    &["Option"],
+                )
+                .is_some()
                 {
-                    let expected_affix_usage = "outer `enum` `fmt` is an affix spec that expects no args and at most 1 placeholder for inner variant display";
-                    if outer_enum {
-                        if list.nested.iter().skip(1).count() != 0 {
-                            return Err(Error::new(
-                                list.nested[1].span(),
-                                expected_affix_usage,
-                            ));
-                        }
-                        // TODO: Check for a single `Display` group?
-                        let fmt_string = match &list.nested[0] {
-                            syn::NestedMeta::Meta(syn::Meta::NameValue(
-                                syn::MetaNameValue {
-                                    path,
-                                    lit: syn::Lit::Str(s),
-                                    ..
-                                },
-                            )) if path
-                                .segments
-                                .first()
-                                .expect("path shouldn't be empty")
-                                .ident
-                                == "fmt" =>
-                            {
-                                s.value()
-                            }
-                            // This one has been checked already in get_meta_fmt() method.
-                            _ => unreachable!(),
-                        };
+                    quote!(match #source {
+                        Some(source) => write!(_derive_more_display_formatter, ": {}", source),
+                        None => Ok(()),
+                    })
+                } else {
+                    quote!(write!(_derive_more_display_formatter, ": {}", #source))
+                };
+                quote_spanned!(meta.span()=> {
+                    #write?;
+                    #write_source
+                })
+            }
+            None => write,
+        };
+        let with_bindings = self.get_field_with_bindings(fields)?;
+        let write = if with_bindings.is_empty() {
+            write
+        } else {
+            quote!({ #with_bindings #write })
+        };
+        let write = if pad.is_none() {
+            write
+        } else {
+            self.requires_pad_helper.set(true);
+            quote_spanned!(meta.span()=> _derive_more_pad(
+                _derive_more_display_formatter,
+                &|_derive_more_display_formatter| #write,
+            ))
+        };
+        if alt.is_none() {
+            return Ok((write, false));
+        }
+        Ok((
+            quote_spanned!(meta.span()=> {
+                let _derive_more_alternate = _derive_more_display_formatter.alternate();
+                #write
+            }),
+            false,
+        ))
+    }
 
-                        let num_placeholders =
-                            Placeholder::parse_fmt_string(&fmt_string).len();
-                        if num_placeholders > 1 {
-                            return Err(Error::new(
-                                list.nested[1].span(),
-                                expected_affix_usage,
-                            ));
-                        }
-                        if num_placeholders == 1 {
-                            return Ok((quote_spanned!(fmt.span()=> #fmt), true));
-                        }
-                    }
-                    let args = list
-                        .nested
-                        .iter()
-                        .skip(1) // skip fmt = "..."
-                        .try_fold(TokenStream::new(), |args, arg| {
-                            let arg = match arg {
-                                syn::NestedMeta::Lit(syn::Lit::Str(s)) => s,
-                                syn::NestedMeta::Meta(syn::Meta::Path(i)) => {
-                                    return Ok(quote_spanned!(list.span()=> #args #i,));
-                                }
-                                _ => {
-                                    return Err(Error::new(
-                                        arg.span(),
-                                        self.get_proper_fmt_syntax(),
-                                    ))
-                                }
-                            };
-                            let arg: TokenStream =
-                                arg.parse().map_err(|e| Error::new(arg.span(), e))?;
-                            Ok(quote_spanned!(list.span()=> #args #arg,))
-                        })?;
+    /// Returns the `write!` of a format string with its arguments.
+    fn get_write<'m>(
+        &self,
+        list: &syn::MetaList,
+        fmt: &syn::LitStr,
+        mut args: impl Iterator<Item = &'m syn::NestedMeta>,
+        fields: &syn::Fields,
+    ) -> Result<TokenStream> {
+        let mut positional_args = vec![];
+        let mut named_args = HashMap::default();
+        let args = args.try_fold(TokenStream::new(), |args, arg| {
+            let span = arg.span();
+            let arg = match arg {
+                syn::NestedMeta::Lit(syn::Lit::Str(s)) => s,
+                syn::NestedMeta::Meta(syn::Meta::Path(i)) => {
+                    positional_args.push(span);
+                    return Ok(quote_spanned!(list.span()=> #args #i,));
+                }
+                _ => return Err(Error::new(span, self.get_proper_fmt_syntax())),
+            };
+            let arg: TokenStream =
+                arg.parse().map_err(|e| Error::new(arg.span(), e))?;
+            match named_arg_name(&arg) {
+                Some(name) => {
+                    named_args.insert(name, span);
+                }
+                None => positional_args.push(span),
+            }
+            Ok(quote_spanned!(list.span()=> #args #arg,))
+        })?;
+        validate_fmt_args(fmt, &positional_args, &named_args)?;
+        let named_args = named_args.into_iter().map(|(name, _)| name).collect();
+        let implicit_args = self.get_implicit_args(fmt, fields, &named_args)?;
+        if mentions_builtin(&args) {
+            self.requires_builtins.set(true);
+        }
+        Ok(
+            quote_spanned!(list.span()=> write!(_derive_more_display_formatter, #fmt, #args #implicit_args)),
+        )
+    }
 
-                    Ok((
-                        quote_spanned!(meta.span()=> write!(_derive_more_display_formatter, #fmt, #args)),
-                        false,
-                    ))
+    /// Returns named arguments for the placeholders in `fmt` referring to
+    /// fields by their name, e.g. `{name}` or `{_0}`, or to one of the
+    /// [`BUILTINS`].
+    ///
+    /// Returns an error if such a placeholder refers to neither of them nor to
+    /// an explicitly passed named argument.
+    fn get_implicit_args(
+        &self,
+        fmt: &syn::LitStr,
+        fields: &syn::Fields,
+        named_args: &HashSet<Ident>,
+    ) -> Result<TokenStream> {
+        let field_names: Vec<_> = fields
+            .iter()
+            .enumerate()
+            .map(|(i, field)| {
+                field
+                    .ident
+                    .as_ref()
+                    .map_or_else(|| format!("_{}", i), ToString::to_string)
+            })
+            .collect();
+
+        let mut implicit_args = TokenStream::new();
+        let mut seen = HashSet::default();
+        for name in Placeholder::parse_named_args(&fmt.value()) {
+            if named_args.iter().any(|arg| arg == name) || !seen.insert(name) {
+                continue;
+            }
+            if self.builtins().contains(&name) {
+                self.requires_builtins.set(true);
+            } else if !field_names.iter().any(|field| field == name) {
+                return Err(Error::new(
+                    fmt.span(),
+                    format!("Unknown field `{}` in format string", name),
+                ));
+            }
+            let name = Ident::new(name, fmt.span());
+            implicit_args.extend(quote!(#name = #name,));
+        }
+        Ok(implicit_args)
+    }
+    /// Returns the [`BUILTINS`] available for the derived type, as `_variant`
+    /// is only available for enums.
+    fn builtins(&self) -> &'static [&'static str] {
+        match self.input.data {
+            syn::Data::Enum(_) => BUILTINS,
+            _ => &BUILTINS[..1],
+        }
+    }
+    /// Returns the statements binding the [`BUILTINS`] available for the
+    /// derived type.
+    fn builtin_bindings(&self) -> Result<TokenStream> {
+        let name = &self.input.ident;
+        let type_name = name.to_string();
+        let variant = match &self.input.data {
+            syn::Data::Enum(e) => {
+                let arms = e
+                    .variants
+                    .iter()
+                    .map(|v| {
+                        let v_name = &v.ident;
+                        let variant_name = self.variant_name(v)?;
+                        Ok(quote!(#name::#v_name { .. } => #variant_name,))
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                quote! {
+                    let _variant: &'static str = match *self {
+                        #(#arms)*
+                    };
                 }
-                _ => Err(Error::new(
-                    list.nested[0].span(),
-                    self.get_proper_fmt_syntax(),
-                )),
-            },
-            _ => Err(Error::new(
-                list.nested[0].span(),
-                self.get_proper_fmt_syntax(),
-            )),
+            }
+            _ => TokenStream::new(),
+        };
+        Ok(quote! {
+            let _type: &'static str = #type_name;
+            #variant
+        })
+    }
+    /// Returns the name of a variant, as changed by a `#[display(rename = "...")]`
+    /// attribute on the variant or a `#[display(rename_all = "...")]` attribute on
+    /// the enum.
+    fn variant_name(&self, variant: &syn::Variant) -> Result<String> {
+        if let Some(meta) = self.find_meta(&variant.attrs, "rename")? {
+            return Ok(self.parse_meta_str(&meta, "rename")?.value());
+        }
+        let meta = match self.find_meta(&self.input.attrs, "rename_all")? {
+            Some(meta) => meta,
+            None => return Ok(variant.ident.to_string()),
+        };
+        let rename_all = self.parse_meta_str(&meta, "rename_all")?;
+        let case = match rename_all.value().as_str() {
+            "snake_case" => Case::Snake,
+            "kebab-case" => Case::Kebab,
+            "SCREAMING_SNAKE_CASE" => Case::ScreamingSnake,
+            "lowercase" => Case::Flat,
+            _ => {
+                return Err(Error::new(
+                    rename_all.span(),
+                    "Unknown case, expected one of: `snake_case`, `kebab-case`, \
+                     `SCREAMING_SNAKE_CASE`, `lowercase`",
+                ))
+            }
+        };
+        Ok(variant.ident.to_string().to_case(case))
+    }
+    /// Parses the string value of an attribute like `#[display(key = "...")]`.
+    fn parse_meta_str(&self, meta: &syn::Meta, key: &str) -> Result<syn::LitStr> {
+        let nested = match meta {
+            syn::Meta::List(list) if list.nested.len() == 1 => &list.nested[0],
+            _ => return Err(self.meta_str_error(meta, key)),
+        };
+        match nested {
+            syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                lit: syn::Lit::Str(value),
+                ..
+            })) => Ok(value.clone()),
+            _ => Err(self.meta_str_error(meta, key)),
         }
     }
-    fn infer_fmt(&self, fields: &syn::Fields, name: &Ident) -> Result<TokenStream> {
+    fn meta_str_error(&self, meta: &syn::Meta, key: &str) -> Error {
+        Error::new(
+            meta.span(),
+            format!(r#"Proper syntax: #[{}({} = "...")]"#, self.trait_attr, key),
+        )
+    }
+    fn infer_fmt(&self, fields: &syn::Fields, name: &str) -> Result<TokenStream> {
         let fields = match fields {
             syn::Fields::Unit => {
-                return Ok(quote!(
-                    _derive_more_display_formatter.write_str(stringify!(#name))
-                ))
+                return Ok(quote!(_derive_more_display_formatter.write_str(#name)))
             }
             syn::Fields::Named(fields) => &fields.named,
             syn::Fields::Unnamed(fields) => &fields.unnamed,
         };
         if fields.is_empty() {
-            return Ok(quote!(
-                _derive_more_display_formatter.write_str(stringify!(#name))
-            ));
+            return Ok(quote!(_derive_more_display_formatter.write_str(#name)));
         } else if fields.len() > 1 {
             return Err(Error::new(
                 fields.span(),
@@ -456,27 +824,129 @@ impl<'a, 'b> State<'a, 'b> {
             ));
         }
 
-        let trait_path = self.trait_path;
-        if let Some(ident) = &fields.iter().next().as_ref().unwrap().ident {
-            Ok(quote!(#trait_path::fmt(#ident, _derive_more_display_formatter)))
+        let field = fields.iter().next().unwrap();
+        let fmt = match self.find_with(&field.attrs)? {
+            Some(with) => quote!(#with),
+            None => {
+                let trait_path = self.trait_path;
+                quote!(#trait_path::fmt)
+            }
+        };
+        if let Some(ident) = &field.ident {
+            Ok(quote!(#fmt(#ident, _derive_more_display_formatter)))
         } else {
-            Ok(quote!(#trait_path::fmt(_0, _derive_more_display_formatter)))
+            Ok(quote!(#fmt(_0, _derive_more_display_formatter)))
+        }
+    }
+    /// Checks that a struct or variant marked with `#[error(transparent)]`
+    /// doesn't specify how to display it, as its `Display` forwards to its
+    /// only field, like the inferred format does.
+    fn check_error_transparent(&self, attrs: &[syn::Attribute]) -> Result<()> {
+        if self.trait_attr != "display" || !is_error_transparent(attrs) {
+            return Ok(());
+        }
+        let meta = match self.find_meta(attrs, "fmt")? {
+            Some(meta) => Some(meta),
+            None => self.find_meta(attrs, "with")?,
+        };
+        match meta {
+            Some(meta) => Err(Error::new(
+                meta.span(),
+                "`#[error(transparent)]` forwards `Display` to the source, so it cannot be combined with `fmt` or `with`",
+            )),
+            None => Ok(()),
+        }
+    }
+    /// Returns the function given by a `#[display(with = "...")]` attribute,
+    /// which is called instead of formatting the annotated item.
+    fn find_with(&self, attrs: &[syn::Attribute]) -> Result<Option<syn::Path>> {
+        let meta = match self.find_meta(attrs, "with")? {
+            Some(meta) => meta,
+            None => return Ok(None),
+        };
+        if let Some(fmt) = self.find_meta(attrs, "fmt")? {
+            return Err(Error::new(
+                fmt.span(),
+                "`fmt` cannot be used together with `with`",
+            ));
         }
+        self.parse_meta_str(&meta, "with")?.parse().map(Some)
+    }
+    /// Returns the statements rebinding each field with a
+    /// `#[display(with = "...")]` attribute to a value formatted with the given
+    /// function.
+    fn get_field_with_bindings(&self, fields: &syn::Fields) -> Result<TokenStream> {
+        let mut bindings = TokenStream::new();
+        for (i, field) in fields.iter().enumerate() {
+            let with = match self.find_with(&field.attrs)? {
+                Some(with) => with,
+                None => continue,
+            };
+            let ident = field
+                .ident
+                .clone()
+                .unwrap_or_else(|| Ident::new(&format!("_{}", i), Span::call_site()));
+            bindings.extend(quote! {
+                let #ident = _derive_more_DisplayAs(|_derive_more_display_formatter| {
+                    #with(#ident, _derive_more_display_formatter)
+                });
+            });
+            self.requires_display_as.set(true);
+        }
+        Ok(bindings)
     }
     fn get_match_arms_and_extra_bounds(&self) -> Result<ParseResult> {
+        let is_enum = match self.input.data {
+            syn::Data::Enum(_) => true,
+            _ => false,
+        };
+        if !is_enum {
+            for key in &["rename_all", "rename"] {
+                if let Some(meta) = self.find_meta(&self.input.attrs, key)? {
+                    return Err(Error::new(
+                        meta.span(),
+                        format!("`{}` can only be used on enums", key),
+                    ));
+                }
+            }
+        }
+        self.check_error_transparent(&self.input.attrs)?;
+        let type_with = self.find_with(&self.input.attrs)?;
         let result: Result<_> = match &self.input.data {
+            // #[display(with = "...")] on the whole type.
+            _ if type_with.is_some() => {
+                let with = type_with.unwrap();
+                Ok(ParseResult {
+                    arms: quote_spanned!(self.input.span()=> _ => #with(self, _derive_more_display_formatter),),
+                    bounds: HashMap::default(),
+                    requires_helper: false,
+                })
+            }
             syn::Data::Enum(e) => {
                 match self
                     .find_meta(&self.input.attrs, "fmt")
-                    .and_then(|m| m.map(|m| self.parse_meta_fmt(&m, true)).transpose())?
+                    .and_then(|m| m.map(|m| self.parse_meta_fmt(&m, &syn::Fields::Unit, None, true)).transpose())?
                 {
                     // #[display(fmt = "no placeholder")] on whole enum.
                     Some((fmt, false)) => {
                         e.variants.iter().try_for_each(|v| {
-                            if let Some(meta) = self.find_meta(&v.attrs, "fmt")? {
+                            if self.trait_attr == "display" && is_error_transparent(&v.attrs) {
+                                return Err(Error::new(
+                                    v.span(),
+                                    "`#[error(transparent)]` variants cannot be used when the whole enum has a format string without a placeholder",
+                                ));
+                            }
+                            let meta = match self.find_meta(&v.attrs, "fmt")? {
+                                Some(meta) => Some(("fmt", meta)),
+                                None => self.find_meta(&v.attrs, "with")?.map(|meta| ("with", meta)),
+                            };
+                            if let Some((attr, meta)) = meta {
                                 Err(Error::new(
                                     meta.span(),
-                                    "`fmt` cannot be used on variant when the whole enum has a format string without a placeholder, maybe you want to add a placeholder?",
+                                    format!(
+                                        "`{}` cannot be used on variant when the whole enum has a format string without a placeholder, maybe you want to add a placeholder?",
+                                        attr,
+                                    ),
                                 ))
                             } else {
                                 Ok(())
@@ -491,20 +961,22 @@ impl<'a, 'b> State<'a, 'b> {
                     }
                     // #[display(fmt = "one placeholder: {}")] on whole enum.
                     Some((outer_fmt, true)) => {
-                        let fmt: Result<TokenStream> = e.variants.iter().try_fold(TokenStream::new(), |arms, v| {
+                        let fmt: Result<TokenStream> = e.variants.iter().enumerate().try_fold(TokenStream::new(), |arms, (i, v)| {
+                            self.check_error_transparent(&v.attrs)?;
                             let matcher = self.get_matcher(&v.fields);
-                            let fmt = if let Some(meta) = self.find_meta(&v.attrs, "fmt")? {
-                                self.parse_meta_fmt(&meta, false)?.0
+                            let fmt = if let Some(with) = self.find_with(&v.attrs)? {
+                                quote!(#with(self, _derive_more_display_formatter))
+                            } else if let Some(meta) = self.find_meta(&v.attrs, "fmt")? {
+                                self.parse_meta_fmt(&meta, &v.fields, Some(i), false)?.0
                             } else {
-                                self.infer_fmt(&v.fields, &v.ident)?
+                                self.infer_fmt(&v.fields, &self.variant_name(v)?)?
                             };
                             let name = &self.input.ident;
                             let v_name = &v.ident;
-                            Ok(quote_spanned!(fmt.span()=> #arms #name::#v_name #matcher => write!(
-                                _derive_more_display_formatter,
-                                #outer_fmt,
-                                _derive_more_DisplayAs(|_derive_more_display_formatter| #fmt)
-                            ),))
+                            Ok(quote_spanned!(fmt.span()=> #arms #name::#v_name #matcher => {
+                                let _derive_more_inner = _derive_more_DisplayAs(|_derive_more_display_formatter| #fmt);
+                                #outer_fmt
+                            },))
                         });
                         let fmt = fmt?;
                         Ok(ParseResult {
@@ -514,19 +986,23 @@ impl<'a, 'b> State<'a, 'b> {
                         })
                     }
                     // No format attribute on whole enum.
-                    None => e.variants.iter().try_fold(ParseResult::default(), |result, v| {
+                    None => e.variants.iter().enumerate().try_fold(ParseResult::default(), |result, (i, v)| {
                         let ParseResult{ arms, mut bounds, requires_helper } = result;
+                        self.check_error_transparent(&v.attrs)?;
                         let matcher = self.get_matcher(&v.fields);
                         let name = &self.input.ident;
                         let v_name = &v.ident;
                         let fmt: TokenStream;
                         let these_bounds: HashMap<_, _>;
 
-                        if let Some(meta) = self.find_meta(&v.attrs, "fmt")? {
-                            fmt = self.parse_meta_fmt(&meta, false)?.0;
-                            these_bounds = self.get_used_type_params_bounds(&v.fields, &meta);
+                        if let Some(with) = self.find_with(&v.attrs)? {
+                            fmt = quote!(#with(self, _derive_more_display_formatter));
+                            these_bounds = HashMap::default();
+                        } else if let Some(meta) = self.find_meta(&v.attrs, "fmt")? {
+                            fmt = self.parse_meta_fmt(&meta, &v.fields, Some(i), false)?.0;
+                            these_bounds = self.get_used_type_params_bounds(&v.fields, Some(i), &meta);
                         } else {
-                            fmt = self.infer_fmt(&v.fields, v_name)?;
+                            fmt = self.infer_fmt(&v.fields, &self.variant_name(v)?)?;
                             these_bounds = self.infer_type_params_bounds(&v.fields);
                         };
                         these_bounds.into_iter().for_each(|(ty, trait_names)| {
@@ -545,10 +1021,10 @@ impl<'a, 'b> State<'a, 'b> {
                 let bounds: HashMap<_, _>;
 
                 if let Some(meta) = self.find_meta(&self.input.attrs, "fmt")? {
-                    fmt = self.parse_meta_fmt(&meta, false)?.0;
-                    bounds = self.get_used_type_params_bounds(&s.fields, &meta);
+                    fmt = self.parse_meta_fmt(&meta, &s.fields, None, false)?.0;
+                    bounds = self.get_used_type_params_bounds(&s.fields, None, &meta);
                 } else {
-                    fmt = self.infer_fmt(&s.fields, name)?;
+                    fmt = self.infer_fmt(&s.fields, &name.to_string())?;
                     bounds = self.infer_type_params_bounds(&s.fields);
                 }
 
@@ -563,10 +1039,15 @@ impl<'a, 'b> State<'a, 'b> {
                     self.find_meta(&self.input.attrs, "fmt")?.ok_or_else(|| {
                         Error::new(
                             self.input.span(),
-                            "Cannot automatically infer format for unions",
+                            "Cannot automatically infer format for unions, use \
+                             `fmt` or `with` to specify it",
                         )
                     })?;
-                let fmt = self.parse_meta_fmt(&meta, false)?.0;
+                // Fields of unions can't be matched on, so they can't be
+                // referred to by their names.
+                let fmt = self
+                    .parse_meta_fmt(&meta, &syn::Fields::Unit, None, false)?
+                    .0;
 
                 Ok(ParseResult {
                     arms: quote_spanned!(self.input.span()=> _ => #fmt,),
@@ -615,15 +1096,18 @@ impl<'a, 'b> State<'a, 'b> {
     fn get_used_type_params_bounds(
         &self,
         fields: &syn::Fields,
+        variant: Option<usize>,
         meta: &syn::Meta,
     ) -> HashMap<syn::Type, HashSet<syn::TraitBound>> {
         if self.type_params.is_empty() {
             return HashMap::default();
         }
 
+        // Fields formatted with a `with` function don't require any bounds.
         let fields_type_params: HashMap<syn::Path, _> = fields
             .iter()
             .enumerate()
+            .filter(|(_, field)| self.find_with(&field.attrs).unwrap_or(None).is_none())
             .filter_map(|(i, field)| {
                 utils::get_if_type_parameter_used_in_type(&self.type_params, &field.ty)
                     .map(|ty| {
@@ -649,55 +1133,81 @@ impl<'a, 'b> State<'a, 'b> {
             // This one has been checked already in get_meta_fmt() method.
             _ => unreachable!(),
         };
-        let fmt_args: HashMap<_, _> = list
-            .nested
-            .iter()
-            .skip(1) // skip fmt = "..."
-            .enumerate()
-            .filter_map(|(i, arg)| match arg {
-                syn::NestedMeta::Lit(syn::Lit::Str(ref s)) => {
-                    syn::parse_str(&s.value()).ok().map(|id| (i, id))
+        // The source appended by `chain` is formatted with `Display`.
+        let mut bounds: HashMap<_, HashSet<_>> = HashMap::default();
+        if list.nested.iter().skip(1).any(|arg| is_flag(arg, "chain")) {
+            // This one has been checked already in get_meta_fmt() method.
+            let source = error_source_field(self.input, variant).unwrap_or(None);
+            let source = source.and_then(|i| {
+                let field = fields.iter().nth(i).unwrap();
+                if self.find_with(&field.attrs).unwrap_or(None).is_some() {
+                    return None;
                 }
-                syn::NestedMeta::Meta(syn::Meta::Path(ref id)) => Some((i, id.clone())),
-                // This one has been checked already in get_meta_fmt() method.
-                _ => unreachable!(),
-            })
-            .collect();
-        if fmt_args.is_empty() {
-            return HashMap::default();
+                let ty = utils::get_single_type_argument(&field.ty, &["Option"])
+                    .unwrap_or(&field.ty);
+                utils::get_if_type_parameter_used_in_type(&self.type_params, ty)
+            });
+            if let Some(ty) = source {
+                bounds
+                    .entry(ty)
+                    .or_default()
+                    .insert(trait_name_to_trait_bound("Display"));
+            }
         }
-        let fmt_string = match &list.nested[0] {
-            syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
-                path,
-                lit: syn::Lit::Str(s),
-                ..
-            })) if path
-                .segments
-                .first()
-                .expect("path shouldn't be empty")
-                .ident
-                == "fmt" =>
-            {
-                s.value()
+
+        // This one has been checked already in get_meta_fmt() method.
+        let fmt_string = fmt_string(list).unwrap();
+        let alt_string = alt_string(list).unwrap_or(None);
+        let templates = Some((fmt_string, fmt_args(list).collect::<Vec<_>>()))
+            .into_iter()
+            .chain(alt_string.map(|alt| (alt, alt_args(list).collect())));
+        for (fmt, args) in templates {
+            let mut positional_args = vec![];
+            let mut named_args = HashMap::default();
+            for arg in args {
+                let arg: TokenStream = match arg {
+                    syn::NestedMeta::Lit(syn::Lit::Str(ref s)) => {
+                        match s.value().parse() {
+                            Ok(arg) => arg,
+                            Err(_) => continue,
+                        }
+                    }
+                    syn::NestedMeta::Meta(syn::Meta::Path(ref id)) => quote!(#id),
+                    // This one has been checked already in get_meta_fmt() method.
+                    _ => unreachable!(),
+                };
+                match named_arg_name(&arg) {
+                    Some(name) => {
+                        let value = arg.into_iter().skip(2).collect();
+                        named_args.insert(name.to_string(), arg_field(value));
+                    }
+                    None => positional_args.push(arg_field(arg)),
+                }
             }
-            // This one has been checked already in get_meta_fmt() method.
-            _ => unreachable!(),
-        };
 
-        Placeholder::parse_fmt_string(&fmt_string).into_iter().fold(
-            HashMap::default(),
-            |mut bounds, pl| {
-                if let Some(arg) = fmt_args.get(&pl.position) {
-                    if fields_type_params.contains_key(arg) {
-                        bounds
-                            .entry(fields_type_params[arg].clone())
-                            .or_insert_with(HashSet::default)
-                            .insert(trait_name_to_trait_bound(pl.trait_name));
+            for pl in Placeholder::parse_fmt_string(&fmt.value()) {
+                // Width and precision arguments are always `usize`, so they
+                // don't require any bounds.
+                let arg = match pl.arg {
+                    Parameter::Positional(position) => {
+                        positional_args.get(position).cloned().unwrap_or(None)
                     }
+                    // Named placeholders not referring to a named argument
+                    // refer to a field.
+                    Parameter::Named(name) => match named_args.get(&name) {
+                        Some(arg) => arg.clone(),
+                        None => syn::parse_str(&name).ok(),
+                    },
+                };
+                if let Some(ty) = arg.and_then(|arg| fields_type_params.get(&arg)) {
+                    bounds
+                        .entry(ty.clone())
+                        .or_insert_with(HashSet::default)
+                        .insert(trait_name_to_trait_bound(pl.trait_name));
                 }
-                bounds
-            },
-        )
+            }
+        }
+        bounds
     }
     fn infer_type_params_bounds(
         &self,
@@ -713,6 +1223,7 @@ impl<'a, 'b> State<'a, 'b> {
         fields
             .iter()
             .take(1)
+            .filter(|field| self.find_with(&field.attrs).unwrap_or(None).is_none())
             .filter_map(|field| {
                 utils::get_if_type_parameter_used_in_type(&self.type_params, &field.ty)
                     .map(|ty| {
@@ -731,30 +1242,357 @@ impl<'a, 'b> State<'a, 'b> {
     }
 }
 
+/// Names bound to information about the derived type, which can be used in
+/// formats like fields: `_type` is the name of the type and `_variant` the name
+/// of the formatted variant.
+const BUILTINS: &[&str] = &["_type", "_variant"];
+
+/// Checks whether formatting arguments refer to any of the [`BUILTINS`].
+fn mentions_builtin(tokens: &TokenStream) -> bool {
+    tokens.clone().into_iter().any(|token| match token {
+        proc_macro2::TokenTree::Ident(ident) => {
+            BUILTINS.iter().any(|builtin| ident == builtin)
+        }
+        proc_macro2::TokenTree::Group(group) => mentions_builtin(&group.stream()),
+        _ => false,
+    })
+}
+
+/// Returns the field a formatting argument refers to, if it's a plain field
+/// possibly behind references or dereferences, e.g. `value`, `&_0` or
+/// `*self.value`.
+fn arg_field(arg: TokenStream) -> Option<syn::Path> {
+    let mut tokens = arg.into_iter().peekable();
+    while let Some(proc_macro2::TokenTree::Punct(punct)) = tokens.peek() {
+        if punct.as_char() != '&' && punct.as_char() != '*' {
+            break;
+        }
+        tokens.next();
+    }
+    let mut tokens: Vec<_> = tokens.collect();
+    if let [proc_macro2::TokenTree::Ident(this), proc_macro2::TokenTree::Punct(dot), _] =
+        &tokens[..]
+    {
+        if this == "self" && dot.as_char() == '.' {
+            tokens.drain(..2);
+        }
+    }
+    let field: TokenStream = tokens.into_iter().collect();
+    syn::parse2(field).ok()
+}
+
+/// Returns the format string of a `#[display(fmt = "...", ...)]` or
+/// `#[display("...", ...)]` attribute.
+fn fmt_string(list: &syn::MetaList) -> Option<&syn::LitStr> {
+    match list.nested.first()? {
+        syn::NestedMeta::Lit(syn::Lit::Str(fmt)) => Some(fmt),
+        syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+            path,
+            lit: syn::Lit::Str(fmt),
+            ..
+        })) if path.is_ident("fmt") => Some(fmt),
+        _ => None,
+    }
+}
+
+/// Checks that all placeholders in a format string can be parsed, so that an
+/// invalid formatting trait is reported on the format string instead of the
+/// generated `write!`.
+pub(crate) fn validate_placeholders(fmt: &syn::LitStr) -> Result<()> {
+    let value = fmt.value();
+    let placeholders = crate::parsing::all_placeholders(&value)
+        .map_err(|e| Error::new(fmt.span(), e))?;
+    for placeholder in placeholders {
+        if crate::parsing::format(placeholder).is_ok() {
+            continue;
+        }
+        let spec = &placeholder[1..placeholder.len() - 1];
+        let ty_len = spec
+            .chars()
+            .rev()
+            .take_while(|c| c.is_ascii_alphabetic() || *c == '?')
+            .count();
+        let ty = &spec[spec.len() - ty_len..];
+        let message = if spec.contains(':') && !ty.is_empty() {
+            format!(
+                "Unknown formatting trait `{}` in placeholder `{}`, expected one of: \
+                 `?`, `x?`, `X?`, `o`, `x`, `X`, `p`, `b`, `e`, `E`",
+                ty, placeholder,
+            )
+        } else {
+            format!("Invalid placeholder `{}`", placeholder)
+        };
+        return Err(Error::new(fmt.span(), message));
+    }
+    Ok(())
+}
+
+/// Checks that the placeholders of a format string refer only to the given
+/// arguments, and that every argument is used.
+///
+/// Named placeholders not referring to one of `named_args` are checked by
+/// `State::get_implicit_args()` instead, as they may refer to fields.
+fn validate_fmt_args(
+    fmt: &syn::LitStr,
+    positional_args: &[Span],
+    named_args: &HashMap<Ident, Span>,
+) -> Result<()> {
+    let value = fmt.value();
+    let mut used_positions = HashSet::default();
+    let mut used_names = HashSet::default();
+    let mut next = 0;
+    for placeholder in crate::parsing::all_placeholders(&value).unwrap() {
+        let (arg, width, precision, _) = crate::parsing::format(placeholder).unwrap();
+        // `.*` takes the precision from the next positional argument, before
+        // the value.
+        if precision == Some("*") {
+            used_positions.insert(next);
+            next += 1;
+        }
+        if arg.is_none() {
+            used_positions.insert(next);
+            next += 1;
+        }
+        for arg in vec![arg, width, precision].into_iter().flatten() {
+            match arg.parse::<usize>() {
+                Ok(position) => {
+                    used_positions.insert(position);
+                }
+                Err(_) => {
+                    used_names.insert(arg);
+                }
+            }
+        }
+    }
+
+    if let Some(position) = used_positions
+        .iter()
+        .filter(|position| **position >= positional_args.len())
+        .max()
+    {
+        let given = match positional_args.len() {
+            0 => "no positional arguments are given".to_owned(),
+            1 => "there is 1 positional argument".to_owned(),
+            n => format!("there are {} positional arguments", n),
+        };
+        return Err(Error::new(
+            fmt.span(),
+            format!(
+                "Invalid reference to positional argument {} ({})",
+                position, given,
+            ),
+        ));
+    }
+    for (position, span) in positional_args.iter().enumerate() {
+        if !used_positions.contains(&position) {
+            return Err(Error::new(
+                *span,
+                "Argument is never used in the format string",
+            ));
+        }
+    }
+    for (name, span) in named_args {
+        if !used_names.iter().any(|used| name == used) {
+            return Err(Error::new(
+                *span,
+                format!(
+                    "Named argument `{}` is never used in the format string",
+                    name
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Returns the formatting arguments of a `#[display(fmt = "...", args...)]`
+/// attribute, skipping the format string itself and the [`FLAGS`].
+///
+/// Arguments following an `alt = "..."` format string are its arguments
+/// instead, see [`alt_args()`].
+fn fmt_args(list: &syn::MetaList) -> impl Iterator<Item = &syn::NestedMeta> {
+    list.nested
+        .iter()
+        .skip(1)
+        .take_while(|arg| !is_alt(arg))
+        .filter(|arg| !is_any_flag(arg))
+}
+
+/// Returns the formatting arguments of the `alt = "..."` format string of a
+/// `#[display(fmt = "...", args..., alt = "...", alt_args...)]` attribute,
+/// skipping the [`FLAGS`].
+fn alt_args(list: &syn::MetaList) -> impl Iterator<Item = &syn::NestedMeta> {
+    list.nested
+        .iter()
+        .skip(1)
+        .skip_while(|arg| !is_alt(arg))
+        .skip(1)
+        .filter(|arg| !is_any_flag(arg))
+}
+
+/// Returns the format string used instead when the alternate flag `{:#}` is
+/// set, given as `alt = "..."` in a `#[display(fmt = "...", args...)]`
+/// attribute, followed by its own arguments.
+fn alt_string(list: &syn::MetaList) -> Result<Option<&syn::LitStr>> {
+    let mut alts = list.nested.iter().skip(1).filter(|arg| is_alt(arg));
+    let alt = match alts.next() {
+        Some(alt) => alt,
+        None => return Ok(None),
+    };
+    if let Some(alt) = alts.next() {
+        return Err(Error::new(alt.span(), "`alt` can only be specified once"));
+    }
+    match alt {
+        syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+            lit: syn::Lit::Str(alt),
+            ..
+        })) => Ok(Some(alt)),
+        _ => Err(Error::new(alt.span(), r#"Proper syntax: alt = "...""#)),
+    }
+}
+
+/// Checks whether an argument of a `#[display(fmt = "...", args...)]`
+/// attribute is the `alt = "..."` format string.
+fn is_alt(arg: &syn::NestedMeta) -> bool {
+    match arg {
+        syn::NestedMeta::Meta(syn::Meta::NameValue(meta)) => meta.path.is_ident("alt"),
+        _ => false,
+    }
+}
+
+/// Flags that can be passed along with the formatting arguments: `pad` applies
+/// the width, fill and alignment to the whole output, and `chain` appends the
+/// source of an error to it.
+const FLAGS: &[&str] = &["pad", "chain"];
+
+/// Checks whether an argument of a `#[display(fmt = "...", args...)]`
+/// attribute is one of the [`FLAGS`].
+fn is_any_flag(arg: &syn::NestedMeta) -> bool {
+    FLAGS.iter().any(|flag| is_flag(arg, flag))
+}
+
+/// Checks whether an argument of a `#[display(fmt = "...", args...)]`
+/// attribute is the given flag.
+fn is_flag(arg: &syn::NestedMeta, flag: &str) -> bool {
+    match arg {
+        syn::NestedMeta::Meta(syn::Meta::Path(path)) => path.is_ident(flag),
+        _ => false,
+    }
+}
+
+/// Returns the name a field is bound to by `State::get_matcher()`.
+fn field_binding(fields: &syn::Fields, index: usize) -> Ident {
+    let field = fields.iter().nth(index).unwrap();
+    field
+        .ident
+        .clone()
+        .unwrap_or_else(|| Ident::new(&format!("_{}", index), Span::call_site()))
+}
+
+/// Checks whether a struct or variant is marked with `#[error(transparent)]`.
+fn is_error_transparent(attrs: &[syn::Attribute]) -> bool {
+    attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("error"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(syn::Meta::List(list)) => Some(list.nested),
+            _ => None,
+        })
+        .flatten()
+        .any(|param| match param {
+            syn::NestedMeta::Meta(syn::Meta::Path(path)) => {
+                path.is_ident("transparent")
+            }
+            _ => false,
+        })
+}
+
+/// Returns the name of a formatting argument passed as `name = value`.
+fn named_arg_name(arg: &TokenStream) -> Option<Ident> {
+    let mut tokens = arg.clone().into_iter();
+    match (tokens.next(), tokens.next(), tokens.next()) {
+        (
+            Some(proc_macro2::TokenTree::Ident(name)),
+            Some(proc_macro2::TokenTree::Punct(eq)),
+            Some(_),
+        ) if eq.as_char() == '=' && eq.spacing() == proc_macro2::Spacing::Alone => {
+            Some(name)
+        }
+        _ => None,
+    }
+}
+
 /// Representation of formatting placeholder.
 #[derive(Debug, PartialEq)]
-struct Placeholder {
-    /// Position of formatting argument to be used for this placeholder.
-    position: usize,
+pub(crate) struct Placeholder {
+    /// Formatting argument to be used for this placeholder.
+    pub(crate) arg: Parameter,
+    /// Formatting argument to be used as the width of this placeholder, if any.
+    pub(crate) width: Option<Parameter>,
+    /// Formatting argument to be used as the precision of this placeholder, if
+    /// any.
+    pub(crate) precision: Option<Parameter>,
     /// Name of [`std::fmt`] trait to be used for rendering this placeholder.
-    trait_name: &'static str,
+    pub(crate) trait_name: &'static str,
+}
+
+/// Reference to a formatting argument from a placeholder.
+#[derive(Debug, PartialEq)]
+pub(crate) enum Parameter {
+    /// Positional argument, either explicitly (e.g. `{1}` or `{:1$}`) or
+    /// implicitly "the next argument" (e.g. `{}` or `{:.*}`).
+    Positional(usize),
+    /// Named argument, e.g. `{name}` or `{:width$}`.
+    Named(String),
+}
+
+impl Parameter {
+    fn parse(arg: &str) -> Self {
+        match arg.parse() {
+            Ok(position) => Parameter::Positional(position),
+            Err(_) => Parameter::Named(arg.to_owned()),
+        }
+    }
 }
 
 impl Placeholder {
+    /// Parses the names of named arguments used by placeholders in a given
+    /// formatting string, including the ones used as `width` and `precision`.
+    fn parse_named_args(s: &str) -> Vec<&str> {
+        crate::parsing::all_placeholders(s)
+            .into_iter()
+            .flatten()
+            .flat_map(|m| {
+                let (arg, width, precision, _) = crate::parsing::format(m).unwrap();
+                vec![arg, width, precision]
+            })
+            .flatten()
+            .filter(|arg| *arg != "*" && arg.parse::<usize>().is_err())
+            .collect()
+    }
+
     /// Parses [`Placeholder`]s from a given formatting string.
-    fn parse_fmt_string(s: &str) -> Vec<Placeholder> {
+    pub(crate) fn parse_fmt_string(s: &str) -> Vec<Placeholder> {
         let mut n = 0;
+        // Assigns "the next argument".
+        // https://doc.rust-lang.org/stable/std/fmt/index.html#positional-parameters
+        let mut next = || {
+            n += 1;
+            Parameter::Positional(n - 1)
+        };
         crate::parsing::all_placeholders(s)
             .into_iter()
             .flatten()
             .map(|m| {
-                let (maybe_arg, maybe_typ) = crate::parsing::format(m).unwrap();
-                let position = maybe_arg.unwrap_or_else(|| {
-                    // Assign "the next argument".
-                    // https://doc.rust-lang.org/stable/std/fmt/index.html#positional-parameters
-                    n += 1;
-                    n - 1
+                let (maybe_arg, maybe_width, maybe_precision, maybe_typ) =
+                    crate::parsing::format(m).unwrap();
+                // `.*` takes the precision from the next argument, before the
+                // value.
+                let precision = maybe_precision.map(|precision| match precision {
+                    "*" => next(),
+                    precision => Parameter::parse(precision),
                 });
+                let arg = maybe_arg.map_or_else(&mut next, Parameter::parse);
                 let typ = maybe_typ.unwrap_or_default();
                 let trait_name = match typ {
                     "" => "Display",
@@ -769,7 +1607,9 @@ impl Placeholder {
                     _ => unreachable!(),
                 };
                 Placeholder {
-                    position,
+                    arg,
+                    width: maybe_width.map(Parameter::parse),
+                    precision,
                     trait_name,
                 }
             })
@@ -816,7 +1656,7 @@ mod regex_placeholder_format_spec {
             ("{9:>8.*}", ""),
             ("{2:.1$x}", "x"),
         ] {
-            let typ = crate::parsing::format(p).unwrap().1.unwrap_or_default();
+            let typ = crate::parsing::format(p).unwrap().3.unwrap_or_default();
             assert_eq!(typ, expected);
         }
     }
@@ -837,6 +1677,9 @@ mod regex_placeholder_format_spec {
             ("{:width$}", ""),
             ("{9:>8.*}", "9"),
             ("{2:.1$x}", "2"),
+            ("{x}", "x"),
+            ("{_0:?}", "_0"),
+            ("{name:>width$}", "name"),
         ] {
             let arg = crate::parsing::format(p)
                 .unwrap()
@@ -854,34 +1697,59 @@ mod placeholder_parse_fmt_string_spec {
 
     #[test]
     fn indicates_position_and_trait_name_for_each_fmt_placeholder() {
-        let fmt_string = "{},{:?},{{}},{{{1:0$}}}-{2:.1$x}{0:#?}{:width$}";
+        let fmt_string =
+            "{},{:?},{{}},{{{1:0$}}}-{2:.1$x}{0:#?}{:width$}{name:.*}{:.*e}";
         assert_eq!(
             Placeholder::parse_fmt_string(&fmt_string),
             vec![
                 Placeholder {
-                    position: 0,
+                    arg: Parameter::Positional(0),
+                    width: None,
+                    precision: None,
                     trait_name: "Display",
                 },
                 Placeholder {
-                    position: 1,
+                    arg: Parameter::Positional(1),
+                    width: None,
+                    precision: None,
                     trait_name: "Debug",
                 },
                 Placeholder {
-                    position: 1,
+                    arg: Parameter::Positional(1),
+                    width: Some(Parameter::Positional(0)),
+                    precision: None,
                     trait_name: "Display",
                 },
                 Placeholder {
-                    position: 2,
+                    arg: Parameter::Positional(2),
+                    width: None,
+                    precision: Some(Parameter::Positional(1)),
                     trait_name: "LowerHex",
                 },
                 Placeholder {
-                    position: 0,
+                    arg: Parameter::Positional(0),
+                    width: None,
+                    precision: None,
                     trait_name: "Debug",
                 },
                 Placeholder {
-                    position: 2,
+                    arg: Parameter::Positional(2),
+                    width: Some(Parameter::Named("width".to_owned())),
+                    precision: None,
                     trait_name: "Display",
                 },
+                Placeholder {
+                    arg: Parameter::Named("name".to_owned()),
+                    width: None,
+                    precision: Some(Parameter::Positional(3)),
+                    trait_name: "Display",
+                },
+                Placeholder {
+                    arg: Parameter::Positional(5),
+                    width: None,
+                    precision: Some(Parameter::Positional(4)),
+                    trait_name: "LowerExp",
+                },
             ],
         )
     }
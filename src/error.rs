@@ -1,10 +1,10 @@
 use proc_macro2::TokenStream;
-use quote::quote;
+use quote::{format_ident, quote};
 use syn::{spanned::Spanned as _, Error, Result};
 
 use crate::utils::{
-    self, AttrParams, DeriveType, FullMetaInfo, HashSet, MetaInfo, MultiFieldData,
-    State,
+    self, error_module, AttrParams, DeriveType, FullMetaInfo, HashSet, MetaInfo,
+    MultiFieldData, State,
 };
 
 pub fn expand(
@@ -15,12 +15,20 @@ pub fn expand(
         ident, generics, ..
     } = input;
 
+    if !cfg!(feature = "std") && !cfg!(feature = "core-error") {
+        return Err(Error::new(
+            input.span(),
+            "derive(Error) requires either the `std` feature, or Rust 1.81 or \
+             later for `core::error::Error`",
+        ));
+    }
+
     let state = State::with_attr_params(
         input,
         trait_name,
-        quote!(::std::error),
+        error_module(),
         trait_name.to_lowercase(),
-        allowed_attr_params(),
+        error_attr_params(),
     )?;
 
     let type_params: HashSet<_> = generics
@@ -32,23 +40,31 @@ pub fn expand(
         })
         .collect();
 
-    let (bounds, source, backtrace) = match state.derive_type {
+    let Rendered {
+        bounds,
+        static_bounds,
+        source,
+        provide,
+        from_impls,
+    } = match state.derive_type {
         DeriveType::Named | DeriveType::Unnamed => render_struct(&type_params, &state)?,
         DeriveType::Enum => render_enum(&type_params, &state)?,
     };
 
+    let error = error_module();
+
     let source = source.map(|source| {
         quote! {
-            fn source(&self) -> Option<&(dyn ::std::error::Error + 'static)> {
+            fn source(&self) -> Option<&(dyn #error::Error + 'static)> {
                 #source
             }
         }
     });
 
-    let backtrace = backtrace.map(|backtrace| {
+    let provide = provide.map(|provide| {
         quote! {
-            fn backtrace(&self) -> Option<&::std::backtrace::Backtrace> {
-                #backtrace
+            fn provide<'__request>(&'__request self, request: &mut #error::Request<'__request>) {
+                #provide
             }
         }
     });
@@ -61,7 +77,7 @@ pub fn expand(
             &generics,
             quote! {
                 where
-                    #ident<#(#generic_parameters),*>: ::std::fmt::Debug + ::std::fmt::Display
+                    #ident<#(#generic_parameters),*>: ::core::fmt::Debug + ::core::fmt::Display
             },
         );
     }
@@ -72,7 +88,18 @@ pub fn expand(
             &generics,
             quote! {
                 where
-                    #(#bounds: ::std::fmt::Debug + ::std::fmt::Display + ::std::error::Error + 'static),*
+                    #(#bounds: ::core::fmt::Debug + ::core::fmt::Display + #error::Error + 'static),*
+            },
+        );
+    }
+
+    if !static_bounds.is_empty() {
+        let static_bounds = static_bounds.iter();
+        generics = utils::add_extra_where_clauses(
+            &generics,
+            quote! {
+                where
+                    #(#static_bounds: 'static),*
             },
         );
     }
@@ -80,36 +107,64 @@ pub fn expand(
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
     let render = quote! {
-        impl#impl_generics ::std::error::Error for #ident#ty_generics #where_clause {
+        impl#impl_generics #error::Error for #ident#ty_generics #where_clause {
             #source
-            #backtrace
+            #provide
         }
+
+        #(#from_impls)*
     };
 
     Ok(render)
 }
 
-fn render_struct(
-    type_params: &HashSet<syn::Ident>,
-    state: &State,
-) -> Result<(HashSet<syn::Type>, Option<TokenStream>, Option<TokenStream>)> {
-    let parsed_fields = parse_fields(type_params, state)?;
+/// Parts of the `Error` implementation, rendered for a struct or an enum.
+struct Rendered {
+    /// Types which have to implement `Error`.
+    bounds: HashSet<syn::Type>,
+    /// Types of the fields exposed via `provide()`, which have to be `'static`.
+    static_bounds: HashSet<syn::Type>,
+    /// Body of the `source()` method.
+    source: Option<TokenStream>,
+    /// Body of the `provide()` method.
+    provide: Option<TokenStream>,
+    /// `From` implementations for the sources marked with `#[error(from)]`.
+    from_impls: Vec<TokenStream>,
+}
 
-    let source = parsed_fields.render_source_as_struct();
-    let backtrace = parsed_fields.render_backtrace_as_struct();
+fn render_struct(type_params: &HashSet<syn::Ident>, state: &State) -> Result<Rendered> {
+    let mut parsed_fields = if state.default_info.info.transparent == Some(true) {
+        parse_transparent_fields(type_params, state, state.input.span())?
+    } else {
+        parse_fields(type_params, state)?
+    };
+    parsed_fields.parse_from(
+        state.default_info.info.from == Some(true),
+        state.input.span(),
+    )?;
 
-    Ok((parsed_fields.bounds, source, backtrace))
+    let source = parsed_fields.render_source_as_struct();
+    let provide = parsed_fields.render_provide_as_struct();
+    let from_impls = parsed_fields.render_from(type_params).into_iter().collect();
+
+    Ok(Rendered {
+        bounds: parsed_fields.bounds,
+        static_bounds: parsed_fields.static_bounds,
+        source,
+        provide,
+        from_impls,
+    })
 }
 
-fn render_enum(
-    type_params: &HashSet<syn::Ident>,
-    state: &State,
-) -> Result<(HashSet<syn::Type>, Option<TokenStream>, Option<TokenStream>)> {
+fn render_enum(type_params: &HashSet<syn::Ident>, state: &State) -> Result<Rendered> {
     let mut bounds = HashSet::default();
+    let mut static_bounds = HashSet::default();
     let mut source_match_arms = Vec::new();
-    let mut backtrace_match_arms = Vec::new();
+    let mut provide_match_arms = Vec::new();
+    let mut from_impls = Vec::new();
 
-    for variant in state.enabled_variant_data().variants {
+    let variant_data = state.enabled_variant_data();
+    for (variant, info) in variant_data.variants.into_iter().zip(variant_data.infos) {
         let default_info = FullMetaInfo {
             enabled: true,
             ..FullMetaInfo::default()
@@ -120,27 +175,35 @@ fn render_enum(
             state.trait_name,
             state.trait_module.clone(),
             state.trait_attr.clone(),
-            allowed_attr_params(),
+            error_attr_params(),
             variant,
             default_info,
         )?;
 
-        let parsed_fields = parse_fields(type_params, &state)?;
+        let mut parsed_fields = if info.info.transparent == Some(true) {
+            parse_transparent_fields(type_params, &state, variant.span())?
+        } else {
+            parse_fields(type_params, &state)?
+        };
+        parsed_fields.parse_from(info.info.from == Some(true), variant.span())?;
 
         if let Some(expr) = parsed_fields.render_source_as_enum_variant_match_arm() {
             source_match_arms.push(expr);
         }
 
-        if let Some(expr) = parsed_fields.render_backtrace_as_enum_variant_match_arm() {
-            backtrace_match_arms.push(expr);
+        if let Some(expr) = parsed_fields.render_provide_as_enum_variant_match_arm() {
+            provide_match_arms.push(expr);
         }
 
-        bounds.extend(parsed_fields.bounds.into_iter());
+        from_impls.extend(parsed_fields.render_from(type_params));
+
+        bounds.extend(parsed_fields.bounds);
+        static_bounds.extend(parsed_fields.static_bounds);
     }
 
-    let render = |match_arms: &mut Vec<TokenStream>| {
+    let render = |match_arms: &mut Vec<TokenStream>, fallback: TokenStream| {
         if !match_arms.is_empty() && match_arms.len() < state.variants.len() {
-            match_arms.push(quote!(_ => None));
+            match_arms.push(quote!(_ => #fallback));
         }
 
         if !match_arms.is_empty() {
@@ -156,18 +219,25 @@ fn render_enum(
         }
     };
 
-    let source = render(&mut source_match_arms);
-    let backtrace = render(&mut backtrace_match_arms);
+    let source = render(&mut source_match_arms, quote!(None));
+    let provide = render(&mut provide_match_arms, quote!({}));
 
-    Ok((bounds, source, backtrace))
+    Ok(Rendered {
+        bounds,
+        static_bounds,
+        source,
+        provide,
+        from_impls,
+    })
 }
 
-fn allowed_attr_params() -> AttrParams {
+/// Returns the attribute parameters supported by `derive(Error)`.
+pub(crate) fn error_attr_params() -> AttrParams {
     AttrParams {
         enum_: vec!["ignore"],
-        struct_: vec!["ignore"],
-        variant: vec!["ignore"],
-        field: vec!["ignore", "source", "backtrace"],
+        struct_: vec!["ignore", "transparent", "from"],
+        variant: vec!["ignore", "transparent", "from"],
+        field: vec!["ignore", "source", "backtrace", "provide", "from"],
     }
 }
 
@@ -175,7 +245,29 @@ struct ParsedFields<'input, 'state> {
     data: MultiFieldData<'input, 'state>,
     source: Option<usize>,
     backtrace: Option<usize>,
+    /// Fields marked with `#[error(provide)]`, exposed by their type in
+    /// `provide()`.
+    provide: Vec<usize>,
     bounds: HashSet<syn::Type>,
+    /// Types of the `provide` fields, which have to be `'static`.
+    static_bounds: HashSet<syn::Type>,
+    /// Whether the struct/variant is marked with `#[error(transparent)]`, so
+    /// `source()` forwards to the `source()` of the `source` field.
+    transparent: bool,
+    /// Whether a `From` implementation is generated for the type of the
+    /// `source` field, as requested with `#[error(from)]`.
+    from: bool,
+    source_type: SourceType,
+}
+
+/// Shape of the type of the `source` field.
+#[derive(Clone, Copy, Default)]
+struct SourceType {
+    /// The source is wrapped in an `Option`, e.g. `Option<io::Error>`.
+    optional: bool,
+    /// The source is a boxed trait object, e.g. `Box<dyn Error + Send + Sync>`,
+    /// which doesn't implement `Error` itself, so it has to be dereferenced.
+    trait_object: bool,
 }
 
 impl<'input, 'state> ParsedFields<'input, 'state> {
@@ -184,35 +276,248 @@ impl<'input, 'state> ParsedFields<'input, 'state> {
             data,
             source: None,
             backtrace: None,
+            provide: Vec::new(),
             bounds: HashSet::default(),
+            static_bounds: HashSet::default(),
+            transparent: false,
+            from: false,
+            source_type: SourceType::default(),
         }
     }
 }
 
 impl<'input, 'state> ParsedFields<'input, 'state> {
+    /// Parses `#[error(from)]`, given whether it's specified for the whole
+    /// struct/variant, in addition to the ones specified for its fields, which
+    /// are only allowed on the `source` field.
+    fn parse_from(&mut self, mut from: bool, span: proc_macro2::Span) -> Result<()> {
+        for (index, info) in self.data.infos.iter().enumerate() {
+            if info.info.from != Some(true) {
+                continue;
+            }
+            if self.source != Some(index) {
+                return Err(Error::new(
+                    self.data.fields[index].span(),
+                    "`#[error(from)]` can only be used on the `source` field",
+                ));
+            }
+            from = true;
+        }
+        if from && self.source.is_none() {
+            return Err(Error::new(
+                span,
+                "`#[error(from)]` requires a `source` field",
+            ));
+        }
+        self.from = from;
+        Ok(())
+    }
+
+    /// Renders the `From` implementation for the type of the `source` field, or
+    /// the type it wraps if it's an `Option`, initializing a `backtrace` field
+    /// with a captured `Backtrace`, and all the other fields with their
+    /// `Default` value.
+    fn render_from(&self, type_params: &HashSet<syn::Ident>) -> Option<TokenStream> {
+        if !self.from {
+            return None;
+        }
+        let state = self.data.state;
+        let source = self.data.field_indexes[self.source?];
+        let backtrace = self.backtrace.map(|index| self.data.field_indexes[index]);
+        let (source_type, source_value) = if self.source_type.optional {
+            // Checked already in `parse_source_type()`.
+            let ty =
+                utils::get_single_type_argument(&state.fields[source].ty, &["Option"])
+                    .unwrap();
+            (ty, quote!(::core::option::Option::Some(source)))
+        } else {
+            (&state.fields[source].ty, quote!(source))
+        };
+
+        let mut default_bounds = HashSet::default();
+        let initializers: Vec<_> = state
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(index, field)| {
+                if index == source {
+                    source_value.clone()
+                } else if Some(index) == backtrace {
+                    quote!(::std::backtrace::Backtrace::capture())
+                } else {
+                    add_bound_if_type_parameter_used_in_type(
+                        &mut default_bounds,
+                        type_params,
+                        &field.ty,
+                    );
+                    quote!(::core::default::Default::default())
+                }
+            })
+            .collect();
+
+        let variant_type = &self.data.variant_type;
+        let body = if state.derive_type == DeriveType::Named {
+            let idents = state.fields.iter().map(|field| &field.ident);
+            quote!(#variant_type{#(#idents: #initializers),*})
+        } else {
+            quote!(#variant_type(#(#initializers),*))
+        };
+
+        let input_type = &state.input.ident;
+        let mut generics = state.input.generics.clone();
+        if !default_bounds.is_empty() {
+            let default_bounds = default_bounds.iter();
+            generics = utils::add_extra_where_clauses(
+                &generics,
+                quote! {
+                    where
+                        #(#default_bounds: ::core::default::Default),*
+                },
+            );
+        }
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+        Some(quote! {
+            #[automatically_derived]
+            impl#impl_generics ::core::convert::From<#source_type> for
+                #input_type#ty_generics #where_clause {
+
+                #[inline]
+                fn from(source: #source_type) -> #input_type#ty_generics {
+                    #body
+                }
+            }
+        })
+    }
+
     fn render_source_as_struct(&self) -> Option<TokenStream> {
         let source = self.source?;
         let ident = &self.data.members[source];
-        Some(render_some(quote!(&#ident)))
+        Some(self.render_source(quote!(&#ident)))
     }
 
     fn render_source_as_enum_variant_match_arm(&self) -> Option<TokenStream> {
         let source = self.source?;
         let pattern = self.data.matcher(&[source], &[quote!(source)]);
-        let expr = render_some(quote!(source));
+        let expr = self.render_source(quote!(source));
         Some(quote!(#pattern => #expr))
     }
 
-    fn render_backtrace_as_struct(&self) -> Option<TokenStream> {
-        let backtrace = self.backtrace?;
-        let backtrace_expr = &self.data.members[backtrace];
-        Some(quote!(Some(&#backtrace_expr)))
+    /// Renders the `source()` for the given reference to the `source` field.
+    fn render_source(&self, source: TokenStream) -> TokenStream {
+        let SourceType {
+            optional,
+            trait_object,
+        } = self.source_type;
+        let error = error_module();
+        if self.transparent && !optional && !trait_object {
+            return quote!(#error::Error::source(#source));
+        }
+
+        let expr = match (optional, trait_object) {
+            (false, false) => render_some(source),
+            (false, true) => render_some(quote!(&**#source)),
+            (true, false) => quote! {
+                (#source).as_ref().map(|source| source as &(dyn #error::Error + 'static))
+            },
+            (true, true) => quote! {
+                (#source).as_ref().map(|source| &**source as &(dyn #error::Error + 'static))
+            },
+        };
+        if self.transparent {
+            quote!(#expr.and_then(#error::Error::source))
+        } else {
+            expr
+        }
+    }
+
+    fn render_provide_as_struct(&self) -> Option<TokenStream> {
+        self.render_provide(|index| {
+            let member = &self.data.members[index];
+            quote!(&#member)
+        })
     }
 
-    fn render_backtrace_as_enum_variant_match_arm(&self) -> Option<TokenStream> {
-        let backtrace = self.backtrace?;
-        let pattern = self.data.matcher(&[backtrace], &[quote!(backtrace)]);
-        Some(quote!(#pattern => Some(backtrace)))
+    fn render_provide_as_enum_variant_match_arm(&self) -> Option<TokenStream> {
+        let mut indexes = Vec::new();
+        let mut bindings = Vec::new();
+        let provide = self.render_provide(|index| {
+            let binding = format_ident!("__{}", index);
+            if !indexes.contains(&index) {
+                indexes.push(index);
+                bindings.push(binding.clone());
+            }
+            quote!(#binding)
+        })?;
+        let pattern = self.data.matcher(&indexes, &bindings);
+        Some(quote!(#pattern => { #provide }))
+    }
+
+    /// Renders the `provide()` body, using `field` to get a reference to the
+    /// field with the given index.
+    ///
+    /// A `source` marked with `#[error(backtrace)]` is asked to provide its
+    /// values first, then the `backtrace` and the `provide` fields are provided.
+    fn render_provide<F>(&self, mut field: F) -> Option<TokenStream>
+    where
+        F: FnMut(usize) -> TokenStream,
+    {
+        let mut statements = Vec::new();
+
+        if let (Some(source), Some(backtrace)) = (self.source, self.backtrace) {
+            if source == backtrace {
+                statements.push(self.render_source_provide(field(source)));
+            }
+        }
+
+        // Backtraces are only available with `std`.
+        if let Some(backtrace) = self.backtrace {
+            if self.source != Some(backtrace) && cfg!(feature = "std") {
+                let backtrace = field(backtrace);
+                statements.push(quote! {
+                    request.provide_ref::<::std::backtrace::Backtrace>(#backtrace);
+                });
+            }
+        }
+
+        for &index in &self.provide {
+            let ty = &self.data.fields[index].ty;
+            let expr = field(index);
+            statements.push(quote!(request.provide_ref::<#ty>(#expr);));
+        }
+
+        if statements.is_empty() {
+            None
+        } else {
+            Some(quote!(#(#statements)*))
+        }
+    }
+
+    /// Renders forwarding of `provide()` to the given reference to the
+    /// `source` field.
+    fn render_source_provide(&self, source: TokenStream) -> TokenStream {
+        let SourceType {
+            optional,
+            trait_object,
+        } = self.source_type;
+        let error = error_module();
+        let provide = if trait_object {
+            quote!(#error::Error::provide(&**source, request);)
+        } else {
+            quote!(#error::Error::provide(source, request);)
+        };
+        if optional {
+            quote! {
+                if let Some(source) = #source {
+                    #provide
+                }
+            }
+        } else {
+            quote! {
+                let source = #source;
+                #provide
+            }
+        }
     }
 }
 
@@ -220,34 +525,81 @@ fn render_some<T>(expr: T) -> TokenStream
 where
     T: quote::ToTokens,
 {
-    quote!(Some(#expr as &(dyn ::std::error::Error + 'static)))
+    let error = error_module();
+    quote!(Some(#expr as &(dyn #error::Error + 'static)))
 }
 
 fn parse_fields<'input, 'state>(
     type_params: &HashSet<syn::Ident>,
     state: &'state State<'input>,
 ) -> Result<ParsedFields<'input, 'state>> {
-    let mut parsed_fields = match state.derive_type {
-        DeriveType::Named => {
-            parse_fields_impl(state, |attr, field, _| {
-                // Unwrapping is safe, cause fields in named struct
-                // always have an ident
-                let ident = field.ident.as_ref().unwrap();
-
-                match attr {
-                    "source" => ident == "source",
-                    "backtrace" => {
-                        ident == "backtrace"
-                            || is_type_path_ends_with_segment(&field.ty, "Backtrace")
-                    }
-                    _ => unreachable!(),
-                }
-            })
+    let ErrorFields { source, backtrace } = parse_error_fields(state)?;
+    let mut parsed_fields = ParsedFields::new(state.enabled_fields_data());
+    parsed_fields.source = source;
+    parsed_fields.backtrace = backtrace;
+    parsed_fields.provide = parsed_fields
+        .data
+        .infos
+        .iter()
+        .enumerate()
+        .filter(|(_, info)| info.info.provide == Some(true))
+        .map(|(index, _)| index)
+        .collect();
+
+    for &index in &parsed_fields.provide {
+        if let Some(ty) = utils::get_if_type_parameter_used_in_type(
+            type_params,
+            &parsed_fields.data.fields[index].ty,
+        ) {
+            parsed_fields.static_bounds.insert(ty);
         }
+    }
+
+    if let Some(source) = parsed_fields.source {
+        let (source_type, ty) = parse_source_type(&state.fields[source].ty)?;
+        parsed_fields.source_type = source_type;
+        if let Some(ty) = ty {
+            add_bound_if_type_parameter_used_in_type(
+                &mut parsed_fields.bounds,
+                type_params,
+                ty,
+            );
+        }
+    }
+
+    Ok(parsed_fields)
+}
+
+/// Indexes of the fields used as the `source` and the `backtrace` of an error
+/// by `derive(Error)`, among the enabled fields of a struct or variant.
+pub(crate) struct ErrorFields {
+    pub(crate) source: Option<usize>,
+    pub(crate) backtrace: Option<usize>,
+}
+
+/// Finds the `source` and the `backtrace` fields of a struct or variant, either
+/// specified with `#[error(...)]` attributes or inferred from the names and
+/// types of the fields.
+pub(crate) fn parse_error_fields(state: &State) -> Result<ErrorFields> {
+    match state.derive_type {
+        DeriveType::Named => parse_error_fields_impl(state, |attr, field, _| {
+            // Unwrapping is safe, cause fields in named struct
+            // always have an ident
+            let ident = field.ident.as_ref().unwrap();
+
+            match attr {
+                "source" => ident == "source",
+                "backtrace" => {
+                    ident == "backtrace"
+                        || is_type_path_ends_with_segment(&field.ty, "Backtrace")
+                }
+                _ => unreachable!(),
+            }
+        }),
 
         DeriveType::Unnamed => {
-            let mut parsed_fields =
-                parse_fields_impl(state, |attr, field, len| match attr {
+            let mut error_fields =
+                parse_error_fields_impl(state, |attr, field, len| match attr {
                     "source" => {
                         len == 1
                             && !is_type_path_ends_with_segment(&field.ty, "Backtrace")
@@ -258,25 +610,74 @@ fn parse_fields<'input, 'state>(
                     _ => unreachable!(),
                 })?;
 
-            parsed_fields.source = parsed_fields
+            error_fields.source = error_fields
                 .source
-                .or_else(|| infer_source_field(&state.fields, &parsed_fields));
+                .or_else(|| infer_error_source_field(state, &error_fields));
 
-            Ok(parsed_fields)
+            Ok(error_fields)
         }
 
         _ => unreachable!(),
-    }?;
-
-    if let Some(source) = parsed_fields.source {
-        add_bound_if_type_parameter_used_in_type(
-            &mut parsed_fields.bounds,
-            type_params,
-            &state.fields[source].ty,
-        );
     }
+}
 
-    Ok(parsed_fields)
+/// Returns the index of the field of a struct, or of the variant with the
+/// given index of an enum, which `derive(Error)` uses as the `source`, among
+/// all of its fields.
+#[cfg(feature = "display")]
+pub(crate) fn error_source_field(
+    input: &syn::DeriveInput,
+    variant: Option<usize>,
+) -> Result<Option<usize>> {
+    let state = State::with_attr_params(
+        input,
+        "Error",
+        error_module(),
+        "error".to_owned(),
+        error_attr_params(),
+    )?;
+    let (state, transparent) = match variant {
+        None => {
+            let transparent = state.default_info.info.transparent == Some(true);
+            (state, transparent)
+        }
+        Some(index) => {
+            let info = &state.variant_states[index].default_info;
+            if !info.enabled {
+                return Ok(None);
+            }
+            let transparent = info.info.transparent == Some(true);
+            let default_info = FullMetaInfo {
+                enabled: true,
+                ..FullMetaInfo::default()
+            };
+            let state = State::from_variant(
+                input,
+                "Error",
+                error_module(),
+                "error".to_owned(),
+                error_attr_params(),
+                state.variants[index],
+                default_info,
+            )?;
+            (state, transparent)
+        }
+    };
+
+    let field_indexes = state.enabled_fields_indexes();
+    let source = if transparent {
+        // `#[error(transparent)]` requires exactly one field being the source.
+        if field_indexes.len() == 1 {
+            Some(0)
+        } else {
+            None
+        }
+    } else if state.derive_type == DeriveType::Enum {
+        None
+    } else {
+        parse_error_fields(&state)?.source
+    };
+    Ok(source.map(|index| field_indexes[index]))
 }
 
 /// Checks if `ty` is [`syn::Type::Path`] and ends with segment matching `tail`
@@ -299,26 +700,30 @@ fn is_type_path_ends_with_segment(ty: &syn::Type, tail: &str) -> bool {
     segment.ident == tail
 }
 
-fn infer_source_field(
-    fields: &[&syn::Field],
-    parsed_fields: &ParsedFields,
+fn infer_error_source_field(
+    state: &State,
+    error_fields: &ErrorFields,
 ) -> Option<usize> {
     // if we have exactly two fields
-    if fields.len() != 2 {
+    if state.fields.len() != 2 {
         return None;
     }
 
     // no source field was specified/inferred
-    if parsed_fields.source.is_some() {
+    if error_fields.source.is_some() {
         return None;
     }
 
     // but one of the fields was specified/inferred as backtrace field
-    if let Some(backtrace) = parsed_fields.backtrace {
+    if let Some(backtrace) = error_fields.backtrace {
         // then infer *other field* as source field
         let source = (backtrace + 1) % 2;
         // unless it was explicitly marked as non-source
-        if parsed_fields.data.infos[source].info.source != Some(false) {
+        let infos = state.enabled_fields_data().infos;
+        if infos
+            .get(source)
+            .map_or(false, |info| info.info.source != Some(false))
+        {
             return Some(source);
         }
     }
@@ -326,10 +731,10 @@ fn infer_source_field(
     None
 }
 
-fn parse_fields_impl<'input, 'state, P>(
-    state: &'state State<'input>,
+fn parse_error_fields_impl<P>(
+    state: &State,
     is_valid_default_field_for_attr: P,
-) -> Result<ParsedFields<'input, 'state>>
+) -> Result<ErrorFields>
 where
     P: Fn(&str, &syn::Field, usize) -> bool,
 {
@@ -341,7 +746,7 @@ where
         .enumerate()
         .map(|(index, (field, info))| (index, *field, info));
 
-    let source = parse_field_impl(
+    let source = parse_error_field_impl(
         &is_valid_default_field_for_attr,
         state.fields.len(),
         iter.clone(),
@@ -349,28 +754,21 @@ where
         |info| info.source,
     )?;
 
-    let backtrace = parse_field_impl(
+    let backtrace = parse_error_field_impl(
         &is_valid_default_field_for_attr,
         state.fields.len(),
-        iter.clone(),
+        iter,
         "backtrace",
         |info| info.backtrace,
     )?;
 
-    let mut parsed_fields = ParsedFields::new(state.enabled_fields_data());
-
-    if let Some((index, _, _)) = source {
-        parsed_fields.source = Some(index);
-    }
-
-    if let Some((index, _, _)) = backtrace {
-        parsed_fields.backtrace = Some(index);
-    }
-
-    Ok(parsed_fields)
+    Ok(ErrorFields {
+        source: source.map(|(index, _, _)| index),
+        backtrace: backtrace.map(|(index, _, _)| index),
+    })
 }
 
-fn parse_field_impl<'a, P, V>(
+fn parse_error_field_impl<'a, P, V>(
     is_valid_default_field_for_attr: &P,
     len: usize,
     iter: impl Iterator<Item = (usize, &'a syn::Field, &'a MetaInfo)> + Clone,
@@ -428,6 +826,100 @@ fn assert_iter_contains_zero_or_one_item<'a>(
     Ok(Some(item))
 }
 
+/// Parses the type of a `source` field, returning its shape and the type
+/// which has to implement `Error`, unless it's a trait object.
+fn parse_source_type(ty: &syn::Type) -> Result<(SourceType, Option<&syn::Type>)> {
+    let (optional, ty) = match utils::get_single_type_argument(ty, &["Option"]) {
+        Some(ty) => (true, ty),
+        None => (false, ty),
+    };
+    if let Some(syn::Type::TraitObject(object)) =
+        utils::get_single_type_argument(ty, &["Box", "Rc", "Arc"])
+    {
+        check_source_trait_object(object)?;
+        let source_type = SourceType {
+            optional,
+            trait_object: true,
+        };
+        return Ok((source_type, None));
+    }
+    match ty {
+        syn::Type::Path(_)
+        | syn::Type::Reference(_)
+        | syn::Type::Paren(_)
+        | syn::Type::Group(_)
+        | syn::Type::Macro(_) => Ok((
+            SourceType {
+                optional,
+                trait_object: false,
+            },
+            Some(ty),
+        )),
+        _ => Err(Error::new(
+            ty.span(),
+            "Unsupported type of source, expected a type implementing `Error`, \
+             an `Option` of it or a boxed `dyn Error` trait object",
+        )),
+    }
+}
+
+/// Checks that a boxed trait object used as source is a `dyn Error + 'static`.
+fn check_source_trait_object(object: &syn::TypeTraitObject) -> Result<()> {
+    let mut is_error = false;
+    for bound in &object.bounds {
+        match bound {
+            syn::TypeParamBound::Trait(bound) => {
+                if let Some(segment) = bound.path.segments.last() {
+                    is_error |= segment.ident == "Error";
+                }
+            }
+            syn::TypeParamBound::Lifetime(lifetime) => {
+                if lifetime.ident != "static" {
+                    return Err(Error::new(
+                        lifetime.span(),
+                        "Trait object used as source has to be `'static`",
+                    ));
+                }
+            }
+        }
+    }
+    if !is_error {
+        return Err(Error::new(
+            object.span(),
+            "Trait object used as source has to be `dyn Error`",
+        ));
+    }
+    Ok(())
+}
+
+/// Parses the fields of a struct/variant marked with `#[error(transparent)]`,
+/// which must have exactly one field used as the `source`.
+fn parse_transparent_fields<'input, 'state>(
+    type_params: &HashSet<syn::Ident>,
+    state: &'state State<'input>,
+    span: proc_macro2::Span,
+) -> Result<ParsedFields<'input, 'state>> {
+    let mut parsed_fields = ParsedFields::new(state.enabled_fields_data());
+    if parsed_fields.data.fields.len() != 1 {
+        return Err(Error::new(
+            span,
+            "`#[error(transparent)]` requires exactly one field",
+        ));
+    }
+    parsed_fields.source = Some(0);
+    parsed_fields.transparent = true;
+    let (source_type, ty) = parse_source_type(&parsed_fields.data.fields[0].ty)?;
+    parsed_fields.source_type = source_type;
+    if let Some(ty) = ty {
+        add_bound_if_type_parameter_used_in_type(
+            &mut parsed_fields.bounds,
+            type_params,
+            ty,
+        );
+    }
+    Ok(parsed_fields)
+}
+
 fn add_bound_if_type_parameter_used_in_type(
     bounds: &mut HashSet<syn::Type>,
     type_params: &HashSet<syn::Ident>,
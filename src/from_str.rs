@@ -1,17 +1,32 @@
-use crate::utils::{SingleFieldData, State};
+use crate::utils::{
+    AttrParams, DeriveType, HashMap, MultiFieldData, SingleFieldData, State,
+};
 use proc_macro2::TokenStream;
-use quote::quote;
-use syn::{parse::Result, DeriveInput};
+use quote::{format_ident, quote};
+use syn::{parse::Result, spanned::Spanned as _, DeriveInput, Error, Fields};
 
 /// Provides the hook to expand `#[derive(FromStr)]` into an implementation of `FromStr`
 pub fn expand(input: &DeriveInput, trait_name: &'static str) -> Result<TokenStream> {
-    let state = State::new(
+    let state = State::with_attr_params(
         input,
         trait_name,
         quote!(::core::str),
-        trait_name.to_lowercase(),
+        String::from("from_str"),
+        AttrParams {
+            enum_: vec!["case_insensitive"],
+            variant: vec!["rename"],
+            struct_: vec!["pattern"],
+            field: vec![],
+        },
     )?;
 
+    if state.derive_type == DeriveType::Enum {
+        return enum_from_str(input, &state);
+    }
+    if let Some(pattern) = &state.default_info.info.pattern {
+        return pattern_from_str(input, &state, pattern);
+    }
+
     // We cannot set defaults for fields, once we do we can remove this check
     if state.fields.len() != 1 || state.enabled_fields().len() != 1 {
         panic_one_field(trait_name);
@@ -44,6 +59,362 @@ pub fn expand(input: &DeriveInput, trait_name: &'static str) -> Result<TokenStre
     })
 }
 
+/// Expands `#[derive(FromStr)]` for an enum consisting only of unit variants.
+///
+/// Variant names are matched exactly, or case-insensitively with
+/// `#[from_str(case_insensitive)]` on the enum.
+fn enum_from_str(input: &DeriveInput, state: &State) -> Result<TokenStream> {
+    let input_type = &input.ident;
+    let vis = &input.vis;
+    let error_type = format_ident!("{}ParseError", input_type);
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let mut names = Vec::with_capacity(state.variant_states.len());
+    for variant_state in &state.variant_states {
+        let variant = variant_state.variant.unwrap();
+        match variant.fields {
+            Fields::Unit => (),
+            _ => {
+                return Err(Error::new(
+                    variant.span(),
+                    "derive(FromStr) only supports enums with unit variants",
+                ))
+            }
+        }
+        let name = variant_state
+            .default_info
+            .info
+            .rename
+            .clone()
+            .unwrap_or_else(|| variant.ident.to_string());
+        if names.iter().any(|(other, _)| *other == name) {
+            return Err(Error::new(
+                variant.span(),
+                format!("Duplicate `{}` name for derive(FromStr)", name),
+            ));
+        }
+        names.push((name, &variant.ident));
+    }
+
+    let case_insensitive = state.default_info.info.case_insensitive == Some(true);
+    if case_insensitive {
+        let mut lowercased = HashMap::default();
+        for (name, variant_ident) in &names {
+            if let Some(other) =
+                lowercased.insert(name.to_ascii_lowercase(), name.as_str())
+            {
+                return Err(Error::new(
+                    variant_ident.span(),
+                    format!(
+                        "`{}` and `{}` only differ in case, so they cannot be \
+                         matched with `case_insensitive`",
+                        other, name,
+                    ),
+                ));
+            }
+        }
+    }
+
+    let arms = names.iter().map(|(name, variant_ident)| {
+        if case_insensitive {
+            quote! {
+                _ if src.eq_ignore_ascii_case(#name) => Ok(#input_type::#variant_ident),
+            }
+        } else {
+            quote!(#name => Ok(#input_type::#variant_ident),)
+        }
+    });
+
+    let expected: Vec<_> = names.iter().map(|(name, _)| name).collect();
+    let message = format!(
+        "Invalid `{}`, expected one of: {}",
+        input_type,
+        expected
+            .iter()
+            .map(|name| format!("`{}`", name))
+            .collect::<Vec<_>>()
+            .join(", "),
+    );
+    let doc = format!(
+        "Error returned when parsing a `{}` from a string fails.",
+        input_type,
+    );
+
+    Ok(quote! {
+        #[doc = #doc]
+        #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+        #vis struct #error_type;
+
+        impl #error_type {
+            /// Returns the names accepted when parsing.
+            #[inline]
+            #vis fn expected(&self) -> &'static [&'static str] {
+                &[#(#expected),*]
+            }
+        }
+
+        impl ::core::fmt::Display for #error_type {
+            #[inline]
+            fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                f.write_str(#message)
+            }
+        }
+
+        impl#impl_generics ::core::str::FromStr for #input_type#ty_generics #where_clause
+        {
+            type Err = #error_type;
+            #[inline]
+            fn from_str(src: &str) -> ::core::result::Result<Self, Self::Err> {
+                match src {
+                    #(#arms)*
+                    _ => Err(#error_type),
+                }
+            }
+        }
+    })
+}
+
+/// Part of a `#[from_str(pattern = "...")]` attribute.
+enum PatternPart {
+    /// Text that has to be matched literally.
+    Literal(String),
+    /// Placeholder to be parsed into the field with the given index.
+    Field(usize),
+}
+
+/// Splits a pattern into its literal text and the fields its placeholders
+/// refer to.
+///
+/// Placeholders use the syntax of formatting strings, so `{x}` refers to the
+/// field `x`, `{_0}` to the first field of a tuple struct and `{}` or `{0}`
+/// to fields by their position.
+fn parse_pattern(
+    pattern: &str,
+    field_names: &[String],
+    span: proc_macro2::Span,
+) -> Result<Vec<PatternPart>> {
+    let placeholders =
+        crate::parsing::all_placeholders(pattern).map_err(|e| Error::new(span, e))?;
+
+    let mut parts = vec![];
+    let mut literal_start = 0;
+    let mut next_position = 0;
+    for placeholder in placeholders {
+        // Placeholders are always slices of the pattern itself.
+        let start = placeholder.as_ptr() as usize - pattern.as_ptr() as usize;
+        let literal = pattern[literal_start..start]
+            .replace("{{", "{")
+            .replace("}}", "}");
+        literal_start = start + placeholder.len();
+
+        if placeholder.contains(':') {
+            return Err(Error::new(
+                span,
+                format!(
+                    "Formatting parameters are not supported in pattern placeholder `{}`",
+                    placeholder,
+                ),
+            ));
+        }
+        let index = match crate::parsing::format(placeholder)
+            .map_err(|_| {
+                Error::new(span, format!("Invalid placeholder `{}`", placeholder))
+            })?
+            .0
+        {
+            None => {
+                next_position += 1;
+                next_position - 1
+            }
+            Some(arg) => match arg.parse::<usize>() {
+                Ok(position) => position,
+                Err(_) => field_names.iter().position(|name| name == arg).ok_or_else(
+                    || Error::new(span, format!("Unknown field `{}` in pattern", arg)),
+                )?,
+            },
+        };
+        if index >= field_names.len() {
+            return Err(Error::new(
+                span,
+                format!("Invalid field position `{}` in pattern", index),
+            ));
+        }
+
+        if literal.is_empty() {
+            if let Some(PatternPart::Field(_)) = parts.last() {
+                return Err(Error::new(
+                    span,
+                    "Placeholders in a pattern have to be separated by some text",
+                ));
+            }
+        } else {
+            parts.push(PatternPart::Literal(literal));
+        }
+        parts.push(PatternPart::Field(index));
+    }
+    let literal = pattern[literal_start..]
+        .replace("{{", "{")
+        .replace("}}", "}");
+    if !literal.is_empty() {
+        parts.push(PatternPart::Literal(literal));
+    }
+
+    for (index, name) in field_names.iter().enumerate() {
+        let uses = parts
+            .iter()
+            .filter(|part| match part {
+                PatternPart::Field(i) => *i == index,
+                PatternPart::Literal(_) => false,
+            })
+            .count();
+        if uses != 1 {
+            return Err(Error::new(
+                span,
+                format!(
+                    "Field `{}` has to be used exactly once in the pattern",
+                    name,
+                ),
+            ));
+        }
+    }
+
+    Ok(parts)
+}
+
+/// Expands `#[derive(FromStr)]` for a struct with a
+/// `#[from_str(pattern = "...")]` attribute.
+///
+/// Each placeholder is parsed with the `FromStr` implementation of its field.
+/// The text matched by a placeholder extends up to the first occurrence of the
+/// literal text following it.
+fn pattern_from_str(
+    input: &DeriveInput,
+    state: &State,
+    pattern: &syn::LitStr,
+) -> Result<TokenStream> {
+    let input_type = &input.ident;
+    let vis = &input.vis;
+    let error_type = format_ident!("{}ParseError", input_type);
+
+    let multi_field_data = state.enabled_fields_data();
+    let MultiFieldData {
+        fields,
+        casted_traits,
+        impl_generics,
+        ty_generics,
+        where_clause,
+        ..
+    } = multi_field_data.clone();
+
+    let field_names: Vec<_> = fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| {
+            field
+                .ident
+                .as_ref()
+                .map_or_else(|| format!("_{}", i), ToString::to_string)
+        })
+        .collect();
+    let parts = parse_pattern(&pattern.value(), &field_names, pattern.span())?;
+
+    let mismatch = quote!(return Err(#error_type { field: None }));
+    let mut vars = vec![quote!(); fields.len()];
+    let mut steps = vec![];
+    // Whether the last placeholder consumes the rest of the input.
+    let mut consumed = false;
+    let mut parts = parts.iter().peekable();
+    while let Some(part) = parts.next() {
+        match part {
+            PatternPart::Literal(literal) => steps.push(quote! {
+                if !rest.starts_with(#literal) {
+                    #mismatch;
+                }
+                let rest = &rest[#literal.len()..];
+            }),
+            PatternPart::Field(index) => {
+                let var = format_ident!("__{}", index);
+                steps.push(match parts.peek() {
+                    Some(PatternPart::Literal(literal)) => quote! {
+                        let (#var, rest) = match rest.find(#literal) {
+                            Some(end) => (&rest[..end], &rest[end..]),
+                            None => #mismatch,
+                        };
+                    },
+                    _ => {
+                        consumed = true;
+                        quote!(let #var = rest;)
+                    }
+                });
+                let field_name = &field_names[*index];
+                let casted_trait = &casted_traits[*index];
+                vars[*index] = quote! {
+                    #casted_trait::from_str(#var).map_err(|_| #error_type {
+                        field: Some(#field_name),
+                    })?
+                };
+            }
+        }
+    }
+    if !consumed {
+        steps.push(quote! {
+            if !rest.is_empty() {
+                #mismatch;
+            }
+        });
+    }
+    let body = multi_field_data.initializer(&vars);
+
+    let doc = format!(
+        "Error returned when parsing a `{}` from a string fails.",
+        input_type,
+    );
+    let mismatch_message = format!(
+        "Invalid `{}`, expected pattern `{}`",
+        input_type,
+        pattern.value()
+    );
+    let field_message = format!("Invalid `{}`, cannot parse field `{{}}`", input_type);
+
+    Ok(quote! {
+        #[doc = #doc]
+        #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+        #vis struct #error_type {
+            field: ::core::option::Option<&'static str>,
+        }
+
+        impl #error_type {
+            /// Returns the name of the field that failed to parse, or `None` if
+            /// the input didn't match the pattern.
+            #[inline]
+            #vis fn field(&self) -> ::core::option::Option<&'static str> {
+                self.field
+            }
+        }
+
+        impl ::core::fmt::Display for #error_type {
+            #[inline]
+            fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                match self.field {
+                    Some(field) => write!(f, #field_message, field),
+                    None => f.write_str(#mismatch_message),
+                }
+            }
+        }
+
+        impl#impl_generics ::core::str::FromStr for #input_type#ty_generics #where_clause
+        {
+            type Err = #error_type;
+            #[inline]
+            fn from_str(src: &str) -> ::core::result::Result<Self, Self::Err> {
+                let rest = src;
+                #(#steps)*
+                Ok(#body)
+            }
+        }
+    })
+}
+
 fn panic_one_field(trait_name: &str) -> ! {
     panic!("Only structs with one field can derive({})", trait_name)
 }
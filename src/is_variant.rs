@@ -1,8 +1,7 @@
-use crate::utils::{AttrParams, DeriveType, State};
-use convert_case::{Case, Casing};
+use crate::utils::{method_ident, variant_method_names, AttrParams, DeriveType, State};
 use proc_macro2::TokenStream;
-use quote::{format_ident, quote};
-use syn::{DeriveInput, Fields, Ident, Result};
+use quote::quote;
+use syn::{DeriveInput, Fields, Result};
 
 pub fn expand(input: &DeriveInput, trait_name: &'static str) -> Result<TokenStream> {
     let state = State::with_attr_params(
@@ -11,8 +10,8 @@ pub fn expand(input: &DeriveInput, trait_name: &'static str) -> Result<TokenStre
         quote!(),
         String::from("is_variant"),
         AttrParams {
-            enum_: vec!["ignore"],
-            variant: vec!["ignore"],
+            enum_: vec!["ignore", "rename", "vis"],
+            variant: vec!["ignore", "rename"],
             struct_: vec!["ignore"],
             field: vec!["ignore"],
         },
@@ -25,15 +24,19 @@ pub fn expand(input: &DeriveInput, trait_name: &'static str) -> Result<TokenStre
     let enum_name = &input.ident;
     let (imp_generics, type_generics, where_clause) = input.generics.split_for_impl();
 
+    let fn_names = variant_method_names(&state, "is_{}")?;
+
+    let vis = state.default_info.info.method_vis();
+
     let mut funcs = vec![];
-    for variant_state in state.enabled_variant_data().variant_states {
+    for (variant_state, fn_name) in state
+        .enabled_variant_data()
+        .variant_states
+        .into_iter()
+        .zip(fn_names)
+    {
         let variant = variant_state.variant.unwrap();
-        let fn_name = Ident::new(
-            &format_ident!("is_{}", variant.ident)
-                .to_string()
-                .to_case(Case::Snake),
-            variant.ident.span(),
-        );
+        let fn_name = method_ident(&fn_name, variant.ident.span())?;
         let variant_ident = &variant.ident;
 
         let data_pattern = match variant.fields {
@@ -42,7 +45,7 @@ pub fn expand(input: &DeriveInput, trait_name: &'static str) -> Result<TokenStre
             Fields::Unit => quote! {},
         };
         let func = quote! {
-            pub fn #fn_name(&self) -> bool {
+            #vis fn #fn_name(&self) -> bool {
                 match self {
                     #enum_name ::#variant_ident #data_pattern => true,
                     _ => false
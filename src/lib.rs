@@ -89,6 +89,7 @@
 //!
 //! 1. [`Display`-like], contains `Display`, `Binary`, `Octal`, `LowerHex`,
 //!    `UpperHex`, `LowerExp`, `UpperExp`, `Pointer`
+//! 2. [`Debug`]
 //!
 //! ### Error-handling traits
 //! These traits are used to define error-types.
@@ -120,7 +121,8 @@
 //!    This is very basic if you need more customization for your constructor, check
 //!    out the [`derive-new`] crate.
 //! 2. [`IsVariant`], for each variant `foo` of an enum type, derives a `is_foo` method.
-//! 3. [`Unwrap`], for each variant `foo` of an enum type, derives an `unwrap_foo` method.
+//! 3. [`Unwrap`], for each variant `foo` of an enum type, derives `unwrap_foo`, `try_unwrap_foo` and `expect_foo` methods.
+//! 4. [`AsVariant`], for each variant `foo` of an enum type, derives `as_foo` and `as_foo_mut` methods.
 //!
 //! ## Generated code
 //!
@@ -165,6 +167,7 @@
 //! [`AsMut`]: https://jeltef.github.io/derive_more/derive_more/as_mut.html
 //!
 //! [`Display`-like]: https://jeltef.github.io/derive_more/derive_more/display.html
+//! [`Debug`]: https://jeltef.github.io/derive_more/derive_more/debug.html
 //!
 //! [`Error`]: https://jeltef.github.io/derive_more/derive_more/error.html
 //!
@@ -182,6 +185,7 @@
 //! [`Constructor`]: https://jeltef.github.io/derive_more/derive_more/constructor.html
 //! [`IsVariant`]: https://jeltef.github.io/derive_more/derive_more/is_variant.html
 //! [`Unwrap`]: https://jeltef.github.io/derive_more/derive_more/unwrap.html
+//! [`AsVariant`]: https://jeltef.github.io/derive_more/derive_more/as_variant.html
 
 #![recursion_limit = "128"]
 
@@ -207,15 +211,20 @@ mod add_like;
 mod as_mut;
 #[cfg(feature = "as_ref")]
 mod as_ref;
+#[cfg(feature = "as_variant")]
+mod as_variant;
 #[cfg(feature = "constructor")]
 mod constructor;
+#[cfg(feature = "debug")]
+mod debug;
 #[cfg(feature = "deref")]
 mod deref;
 #[cfg(feature = "deref_mut")]
 mod deref_mut;
 #[cfg(feature = "display")]
 mod display;
-#[cfg(feature = "error")]
+#[cfg(any(feature = "display", feature = "error"))]
+#[cfg_attr(not(feature = "error"), allow(dead_code))]
 mod error;
 #[cfg(feature = "from")]
 mod from;
@@ -239,7 +248,7 @@ mod mul_helpers;
 mod mul_like;
 #[cfg(feature = "not")]
 mod not_like;
-#[cfg(feature = "display")]
+#[cfg(any(feature = "display", feature = "from_str"))]
 #[allow(ellipsis_inclusive_range_patterns)]
 #[allow(clippy::all)]
 mod parsing;
@@ -289,7 +298,14 @@ create_derive!("from", from, From, from_derive, from);
 
 create_derive!("into", into, Into, into_derive, into);
 
-create_derive!("constructor", constructor, Constructor, constructor_derive);
+create_derive!(
+    "constructor",
+    constructor,
+    Constructor,
+    constructor_derive,
+    constructor,
+    new,
+);
 
 create_derive!("not", not_like, Not, not_derive);
 create_derive!("not", not_like, Neg, neg_derive);
@@ -368,7 +384,7 @@ create_derive!("sum", sum_like, Product, product_derive);
 
 create_derive!("error", error, Error, error_derive, error);
 
-create_derive!("from_str", from_str, FromStr, from_str_derive);
+create_derive!("from_str", from_str, FromStr, from_str_derive, from_str);
 
 create_derive!("display", display, Display, display_derive, display);
 create_derive!("display", display, Binary, binary_derive, binary);
@@ -380,6 +396,8 @@ create_derive!("display", display, UpperExp, upper_exp_derive, upper_exp);
 create_derive!("display", display, Pointer, pointer_derive, pointer);
 create_derive!("display", display, DebugCustom, debug_custom_derive, debug);
 
+create_derive!("debug", debug, Debug, debug_derive, debug);
+
 create_derive!("index", index, Index, index_derive, index);
 create_derive!(
     "index_mut",
@@ -419,4 +437,12 @@ create_derive!(
     is_variant
 );
 
+create_derive!(
+    "as_variant",
+    as_variant,
+    AsVariant,
+    as_variant_derive,
+    as_variant
+);
+
 create_derive!("unwrap", unwrap, Unwrap, unwrap_derive, unwrap);
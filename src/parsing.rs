@@ -277,40 +277,87 @@ fn __parse_arg<'input>(
     __input: &'input str,
     __state: &mut ParseState<'input>,
     __pos: usize,
-) -> RuleResult<usize> {
+) -> RuleResult<&'input str> {
     #![allow(non_snake_case, unused)]
     {
         let __seq_res = {
             let str_start = __pos;
             match {
-                let mut __repeat_pos = __pos;
-                let mut __repeat_value = vec![];
-                loop {
-                    let __pos = __repeat_pos;
-                    let __step_res = if __input.len() > __pos {
-                        let (__ch, __next) = char_range_at(__input, __pos);
-                        match __ch {
-                            '0'...'9' => Matched(__next, ()),
-                            _ => __state.mark_failure(__pos, "[0-9]"),
+                let __choice_res = {
+                    let mut __repeat_pos = __pos;
+                    let mut __repeat_value = vec![];
+                    loop {
+                        let __pos = __repeat_pos;
+                        let __step_res = if __input.len() > __pos {
+                            let (__ch, __next) = char_range_at(__input, __pos);
+                            match __ch {
+                                '0'...'9' => Matched(__next, ()),
+                                _ => __state.mark_failure(__pos, "[0-9]"),
+                            }
+                        } else {
+                            __state.mark_failure(__pos, "[0-9]")
+                        };
+                        match __step_res {
+                            Matched(__newpos, __value) => {
+                                __repeat_pos = __newpos;
+                                __repeat_value.push(__value);
+                            }
+                            Failed => {
+                                break;
+                            }
                         }
+                    }
+                    if __repeat_value.len() >= 1 {
+                        Matched(__repeat_pos, ())
                     } else {
-                        __state.mark_failure(__pos, "[0-9]")
-                    };
-                    match __step_res {
-                        Matched(__newpos, __value) => {
-                            __repeat_pos = __newpos;
-                            __repeat_value.push(__value);
-                        }
-                        Failed => {
-                            break;
+                        Failed
+                    }
+                };
+                match __choice_res {
+                    Matched(__pos, __value) => Matched(__pos, __value),
+                    Failed => {
+                        let __seq_res = if __input.len() > __pos {
+                            let (__ch, __next) = char_range_at(__input, __pos);
+                            match __ch {
+                                'A'...'Z' | 'a'...'z' | '_' => Matched(__next, ()),
+                                _ => __state.mark_failure(__pos, "[A-Za-z_]"),
+                            }
+                        } else {
+                            __state.mark_failure(__pos, "[A-Za-z_]")
+                        };
+                        match __seq_res {
+                            Matched(__pos, _) => {
+                                let mut __repeat_pos = __pos;
+                                loop {
+                                    let __pos = __repeat_pos;
+                                    let __step_res = if __input.len() > __pos {
+                                        let (__ch, __next) =
+                                            char_range_at(__input, __pos);
+                                        match __ch {
+                                            'A'...'Z' | 'a'...'z' | '0'...'9' | '_' => {
+                                                Matched(__next, ())
+                                            }
+                                            _ => __state
+                                                .mark_failure(__pos, "[A-Za-z0-9_]"),
+                                        }
+                                    } else {
+                                        __state.mark_failure(__pos, "[A-Za-z0-9_]")
+                                    };
+                                    match __step_res {
+                                        Matched(__newpos, __value) => {
+                                            __repeat_pos = __newpos;
+                                        }
+                                        Failed => {
+                                            break;
+                                        }
+                                    }
+                                }
+                                Matched(__repeat_pos, ())
+                            }
+                            Failed => Failed,
                         }
                     }
                 }
-                if __repeat_value.len() >= 1 {
-                    Matched(__repeat_pos, ())
-                } else {
-                    Failed
-                }
             } {
                 Matched(__newpos, _) => {
                     Matched(__newpos, &__input[str_start..__newpos])
@@ -319,7 +366,7 @@ fn __parse_arg<'input>(
             }
         };
         match __seq_res {
-            Matched(__pos, n) => Matched(__pos, { n.parse().unwrap() }),
+            Matched(__pos, n) => Matched(__pos, { n }),
             Failed => Failed,
         }
     }
@@ -428,11 +475,163 @@ fn __parse_ty<'input>(
     }
 }
 
-fn __parse_format_spec<'input>(
+fn __parse_count<'input>(
     __input: &'input str,
     __state: &mut ParseState<'input>,
     __pos: usize,
 ) -> RuleResult<Option<&'input str>> {
+    #![allow(non_snake_case, unused)]
+    {
+        let __choice_res = {
+            let __seq_res = {
+                let str_start = __pos;
+                match {
+                    let mut __repeat_pos = __pos;
+                    let mut __repeat_value = vec![];
+                    loop {
+                        let __pos = __repeat_pos;
+                        let __step_res = if __input.len() > __pos {
+                            let (__ch, __next) = char_range_at(__input, __pos);
+                            match __ch {
+                                'A'...'Z' | 'a'...'z' | '0'...'9' | '_' => {
+                                    Matched(__next, ())
+                                }
+                                _ => __state.mark_failure(__pos, "[A-Za-z0-9_]"),
+                            }
+                        } else {
+                            __state.mark_failure(__pos, "[A-Za-z0-9_]")
+                        };
+                        match __step_res {
+                            Matched(__newpos, __value) => {
+                                __repeat_pos = __newpos;
+                                __repeat_value.push(__value);
+                            }
+                            Failed => {
+                                break;
+                            }
+                        }
+                    }
+                    if __repeat_value.len() >= 1 {
+                        Matched(__repeat_pos, ())
+                    } else {
+                        Failed
+                    }
+                } {
+                    Matched(__newpos, _) => {
+                        Matched(__newpos, &__input[str_start..__newpos])
+                    }
+                    Failed => Failed,
+                }
+            };
+            match __seq_res {
+                Matched(__pos, n) => {
+                    let __seq_res = slice_eq(__input, __state, __pos, "$");
+                    match __seq_res {
+                        Matched(__pos, _) => Matched(__pos, { Some(n) }),
+                        Failed => Failed,
+                    }
+                }
+                Failed => Failed,
+            }
+        };
+        match __choice_res {
+            Matched(__pos, __value) => Matched(__pos, __value),
+            Failed => {
+                let __seq_res = {
+                    let mut __repeat_pos = __pos;
+                    let mut __repeat_value = vec![];
+                    loop {
+                        let __pos = __repeat_pos;
+                        let __step_res = if __input.len() > __pos {
+                            let (__ch, __next) = char_range_at(__input, __pos);
+                            match __ch {
+                                '0'...'9' => Matched(__next, ()),
+                                _ => __state.mark_failure(__pos, "[0-9]"),
+                            }
+                        } else {
+                            __state.mark_failure(__pos, "[0-9]")
+                        };
+                        match __step_res {
+                            Matched(__newpos, __value) => {
+                                __repeat_pos = __newpos;
+                                __repeat_value.push(__value);
+                            }
+                            Failed => {
+                                break;
+                            }
+                        }
+                    }
+                    if __repeat_value.len() >= 1 {
+                        Matched(__repeat_pos, ())
+                    } else {
+                        Failed
+                    }
+                };
+                match __seq_res {
+                    Matched(__pos, _) => Matched(__pos, { None }),
+                    Failed => Failed,
+                }
+            }
+        }
+    }
+}
+
+fn __parse_precision<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<Option<&'input str>> {
+    #![allow(non_snake_case, unused)]
+    {
+        let __choice_res = {
+            let __seq_res = slice_eq(__input, __state, __pos, ".");
+            match __seq_res {
+                Matched(__pos, _) => {
+                    let __seq_res = {
+                        let str_start = __pos;
+                        match slice_eq(__input, __state, __pos, "*") {
+                            Matched(__newpos, _) => {
+                                Matched(__newpos, &__input[str_start..__newpos])
+                            }
+                            Failed => Failed,
+                        }
+                    };
+                    match __seq_res {
+                        Matched(__pos, n) => Matched(__pos, { Some(n) }),
+                        Failed => Failed,
+                    }
+                }
+                Failed => Failed,
+            }
+        };
+        match __choice_res {
+            Matched(__pos, __value) => Matched(__pos, __value),
+            Failed => {
+                let __seq_res = slice_eq(__input, __state, __pos, ".");
+                match __seq_res {
+                    Matched(__pos, _) => {
+                        let __seq_res = __parse_count(__input, __state, __pos);
+                        match __seq_res {
+                            Matched(__pos, n) => Matched(__pos, { n }),
+                            Failed => Failed,
+                        }
+                    }
+                    Failed => Failed,
+                }
+            }
+        }
+    }
+}
+
+fn __parse_format_spec<'input>(
+    __input: &'input str,
+    __state: &mut ParseState<'input>,
+    __pos: usize,
+) -> RuleResult<(
+    Option<&'input str>,
+    Option<&'input str>,
+    Option<&'input str>,
+)> {
     #![allow(non_snake_case, unused)]
     {
         let __seq_res = slice_eq(__input, __state, __pos, ":");
@@ -505,116 +704,21 @@ fn __parse_format_spec<'input>(
                                 match __seq_res {
                                     Matched(__pos, _) => {
                                         let __seq_res = match {
-                                            let __choice_res = {
-                                                let __seq_res = {
-                                                    let mut __repeat_pos = __pos;
-                                                    let mut __repeat_value = vec![];
-                                                    loop {
-                                                        let __pos = __repeat_pos;
-                                                        let __step_res =
-                                                            if __input.len() > __pos {
-                                                                let (__ch, __next) =
-                                                                    char_range_at(
-                                                                        __input, __pos,
-                                                                    );
-                                                                match __ch {
-                                                                    'A'...'Z'
-                                                                    | 'a'...'z'
-                                                                    | '0'...'9'
-                                                                    | '_' => Matched(
-                                                                        __next,
-                                                                        (),
-                                                                    ),
-                                                                    _ => __state
-                                                                        .mark_failure(
-                                                                        __pos,
-                                                                        "[A-Za-z0-9_]",
-                                                                    ),
-                                                                }
-                                                            } else {
-                                                                __state.mark_failure(
-                                                                    __pos,
-                                                                    "[A-Za-z0-9_]",
-                                                                )
-                                                            };
-                                                        match __step_res {
-                                                            Matched(
-                                                                __newpos,
-                                                                __value,
-                                                            ) => {
-                                                                __repeat_pos = __newpos;
-                                                                __repeat_value
-                                                                    .push(__value);
-                                                            }
-                                                            Failed => {
-                                                                break;
-                                                            }
-                                                        }
-                                                    }
-                                                    if __repeat_value.len() >= 1 {
-                                                        Matched(__repeat_pos, ())
-                                                    } else {
-                                                        Failed
-                                                    }
-                                                };
-                                                match __seq_res {
-                                                    Matched(__pos, _) => slice_eq(
+                                            let __seq_res =
+                                                slice_eq(__input, __state, __pos, "0");
+                                            match __seq_res {
+                                                Matched(__pos, _) => {
+                                                    __state.suppress_fail += 1;
+                                                    let __assert_res = slice_eq(
                                                         __input, __state, __pos, "$",
-                                                    ),
-                                                    Failed => Failed,
-                                                }
-                                            };
-                                            match __choice_res {
-                                                Matched(__pos, __value) => {
-                                                    Matched(__pos, __value)
-                                                }
-                                                Failed => {
-                                                    let mut __repeat_pos = __pos;
-                                                    let mut __repeat_value = vec![];
-                                                    loop {
-                                                        let __pos = __repeat_pos;
-                                                        let __step_res = if __input
-                                                            .len()
-                                                            > __pos
-                                                        {
-                                                            let (__ch, __next) =
-                                                                char_range_at(
-                                                                    __input, __pos,
-                                                                );
-                                                            match __ch {
-                                                                '0'...'9' => {
-                                                                    Matched(__next, ())
-                                                                }
-                                                                _ => __state
-                                                                    .mark_failure(
-                                                                        __pos, "[0-9]",
-                                                                    ),
-                                                            }
-                                                        } else {
-                                                            __state.mark_failure(
-                                                                __pos, "[0-9]",
-                                                            )
-                                                        };
-                                                        match __step_res {
-                                                            Matched(
-                                                                __newpos,
-                                                                __value,
-                                                            ) => {
-                                                                __repeat_pos = __newpos;
-                                                                __repeat_value
-                                                                    .push(__value);
-                                                            }
-                                                            Failed => {
-                                                                break;
-                                                            }
-                                                        }
-                                                    }
-                                                    if __repeat_value.len() >= 1 {
-                                                        Matched(__repeat_pos, ())
-                                                    } else {
-                                                        Failed
+                                                    );
+                                                    __state.suppress_fail -= 1;
+                                                    match __assert_res {
+                                                        Failed => Matched(__pos, ()),
+                                                        Matched(..) => Failed,
                                                     }
                                                 }
+                                                Failed => Failed,
                                             }
                                         } {
                                             Matched(__newpos, _) => {
@@ -624,76 +728,33 @@ fn __parse_format_spec<'input>(
                                         };
                                         match __seq_res {
                                             Matched(__pos, _) => {
-                                                let __seq_res = match slice_eq(
-                                                    __input, __state, __pos, "0",
+                                                let __seq_res = match __parse_count(
+                                                    __input, __state, __pos,
                                                 ) {
-                                                    Matched(__newpos, _) => {
-                                                        Matched(__newpos, ())
+                                                    Matched(__newpos, __value) => {
+                                                        Matched(__newpos, Some(__value))
                                                     }
-                                                    Failed => Matched(__pos, ()),
+                                                    Failed => Matched(__pos, None),
                                                 };
                                                 match __seq_res {
-                                                    Matched(__pos, _) => {
-                                                        let __seq_res = match {
-                                                            let __seq_res = slice_eq(
-                                                                __input, __state,
-                                                                __pos, ".",
-                                                            );
-                                                            match __seq_res {
-                                                                Matched(__pos, _) => {
-                                                                    let __choice_res = {
-                                                                        let __seq_res = {
-                                                                            let mut
-                                                                            __repeat_pos =
-                                                                                __pos;
-                                                                            let mut
-                                                                            __repeat_value =
-                                                                                vec![];
-                                                                            loop {
-                                                                                let __pos = __repeat_pos ;
-                                                                                let __step_res = if __input . len ( ) > __pos { let ( __ch , __next ) = char_range_at ( __input , __pos ) ; match __ch { 'A' ... 'Z' | 'a' ... 'z' | '0' ... '9' | '_' => Matched ( __next , ( ) ) , _ => __state . mark_failure ( __pos , "[A-Za-z0-9_]" ) , } } else { __state . mark_failure ( __pos , "[A-Za-z0-9_]" ) } ;
-                                                                                match __step_res { Matched ( __newpos , __value ) => { __repeat_pos = __newpos ; __repeat_value . push ( __value ) ; } , Failed => { break ; } }
-                                                                            }
-                                                                            if __repeat_value . len ( ) >= 1 { Matched ( __repeat_pos , ( ) ) } else { Failed }
-                                                                        };
-                                                                        match __seq_res { Matched ( __pos , _ ) => { slice_eq ( __input , __state , __pos , "$" ) } Failed => Failed , }
-                                                                    };
-                                                                    match __choice_res {
-                                                                        Matched(
-                                                                            __pos,
-                                                                            __value,
-                                                                        ) => Matched(
-                                                                            __pos,
-                                                                            __value,
-                                                                        ),
-                                                                        Failed => {
-                                                                            let __choice_res = {
-                                                                                let mut __repeat_pos = __pos ;
-                                                                                let mut
-                                                                                __repeat_value = vec![];
-                                                                                loop {
-                                                                                    let __pos = __repeat_pos ;
-                                                                                    let __step_res = if __input . len ( ) > __pos { let ( __ch , __next ) = char_range_at ( __input , __pos ) ; match __ch { '0' ... '9' => Matched ( __next , ( ) ) , _ => __state . mark_failure ( __pos , "[0-9]" ) , } } else { __state . mark_failure ( __pos , "[0-9]" ) } ;
-                                                                                    match __step_res { Matched ( __newpos , __value ) => { __repeat_pos = __newpos ; __repeat_value . push ( __value ) ; } , Failed => { break ; } }
-                                                                                }
-                                                                                if __repeat_value . len ( ) >= 1 { Matched ( __repeat_pos , ( ) ) } else { Failed }
-                                                                            };
-                                                                            match __choice_res { Matched ( __pos , __value ) => Matched ( __pos , __value ) , Failed => slice_eq ( __input , __state , __pos , "*" ) }
-                                                                        }
-                                                                    }
+                                                    Matched(__pos, w) => {
+                                                        let __seq_res =
+                                                            match __parse_precision(
+                                                                __input, __state, __pos,
+                                                            ) {
+                                                                Matched(
+                                                                    __newpos,
+                                                                    __value,
+                                                                ) => Matched(
+                                                                    __newpos,
+                                                                    Some(__value),
+                                                                ),
+                                                                Failed => {
+                                                                    Matched(__pos, None)
                                                                 }
-                                                                Failed => Failed,
-                                                            }
-                                                        } {
-                                                            Matched(__newpos, _) => {
-                                                                Matched(__newpos, ())
-                                                            }
-                                                            Failed => {
-                                                                Matched(__pos, ())
-                                                            }
-                                                        };
+                                                            };
                                                         match __seq_res {
-                                                            Matched(__pos, _) => {
+                                                            Matched(__pos, p) => {
                                                                 let __seq_res =
                                                                     match __parse_ty(
                                                                         __input,
@@ -721,7 +782,9 @@ fn __parse_format_spec<'input>(
                                                                         n,
                                                                     ) => Matched(
                                                                         __pos,
-                                                                        { n },
+                                                                        {
+                                                                            (w.and_then(|x| x), p.and_then(|x| x), n)
+                                                                        },
                                                                     ),
                                                                     Failed => Failed,
                                                                 }
@@ -800,7 +863,12 @@ fn __parse_format<'input>(
     __input: &'input str,
     __state: &mut ParseState<'input>,
     __pos: usize,
-) -> RuleResult<(Option<usize>, Option<&'input str>)> {
+) -> RuleResult<(
+    Option<&'input str>,
+    Option<&'input str>,
+    Option<&'input str>,
+    Option<&'input str>,
+)> {
     #![allow(non_snake_case, unused)]
     {
         let __seq_res = slice_eq(__input, __state, __pos, "{");
@@ -823,9 +891,12 @@ fn __parse_format<'input>(
                             Matched(__pos, o) => {
                                 let __seq_res = slice_eq(__input, __state, __pos, "}");
                                 match __seq_res {
-                                    Matched(__pos, _) => {
-                                        Matched(__pos, { (n, o.and_then(|x| x)) })
-                                    }
+                                    Matched(__pos, _) => Matched(__pos, {
+                                        match o {
+                                            Some((w, p, t)) => (n, w, p, t),
+                                            None => (n, None, None, None),
+                                        }
+                                    }),
                                     Failed => Failed,
                                 }
                             }
@@ -862,7 +933,12 @@ pub fn all_placeholders<'input>(__input: &'input str) -> ParseResult<Vec<&'input
 
 pub fn format<'input>(
     __input: &'input str,
-) -> ParseResult<(Option<usize>, Option<&'input str>)> {
+) -> ParseResult<(
+    Option<&'input str>,
+    Option<&'input str>,
+    Option<&'input str>,
+    Option<&'input str>,
+)> {
     #![allow(non_snake_case, unused)]
     let mut __state = ParseState::new();
     match __parse_format(__input, &mut __state, 0) {
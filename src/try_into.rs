@@ -1,10 +1,10 @@
 use crate::utils::{
-    add_extra_generic_param, numbered_vars, AttrParams, DeriveType, MultiFieldData,
-    State,
+    add_extra_generic_param, error_module, numbered_vars, AttrParams, DeriveType,
+    MultiFieldData, State,
 };
 use proc_macro2::TokenStream;
-use quote::{quote, ToTokens};
-use syn::{DeriveInput, Result};
+use quote::{format_ident, quote, ToTokens};
+use syn::{spanned::Spanned as _, DeriveInput, Error, Result};
 
 use crate::utils::HashMap;
 
@@ -17,7 +17,7 @@ pub fn expand(input: &DeriveInput, trait_name: &'static str) -> Result<TokenStre
         quote!(::core::convert),
         String::from("try_into"),
         AttrParams {
-            enum_: vec!["ignore", "owned", "ref", "ref_mut"],
+            enum_: vec!["ignore", "owned", "ref", "ref_mut", "error"],
             variant: vec!["ignore", "owned", "ref", "ref_mut"],
             struct_: vec!["ignore", "owned", "ref", "ref_mut"],
             field: vec!["ignore"],
@@ -28,6 +28,20 @@ pub fn expand(input: &DeriveInput, trait_name: &'static str) -> Result<TokenStre
         "Only enums can derive TryInto"
     );
 
+    let error_type = match state.default_info.info.error.as_ref().map(String::as_str) {
+        None => None,
+        Some("generated") => Some(format_ident!("{}TryIntoError", input.ident)),
+        Some(mode) => {
+            return Err(Error::new(
+                input.span(),
+                format!(
+                    "Unknown error mode `{}` for derive(TryInto), only `generated` is supported",
+                    mode,
+                ),
+            ))
+        }
+    };
+
     let mut variants_per_types = HashMap::default();
 
     for variant_state in state.enabled_variant_data().variant_states {
@@ -46,6 +60,9 @@ pub fn expand(input: &DeriveInput, trait_name: &'static str) -> Result<TokenStre
     }
 
     let mut tokens = TokenStream::new();
+    if let Some(error_type) = &error_type {
+        generated_error(&input.ident, &input.vis, error_type).to_tokens(&mut tokens);
+    }
 
     for ((ref_type, ref original_types), ref multi_field_datas) in variants_per_types {
         let input_type = &input.ident;
@@ -87,10 +104,7 @@ pub fn expand(input: &DeriveInput, trait_name: &'static str) -> Result<TokenStre
                     d.variant_name.expect("Somehow there was no variant name")
                 )
             })
-            .collect::<Vec<_>>()
-            .join(", ");
-        let message =
-            format!("Only {} can be converted to {}", variant_names, output_type);
+            .collect::<Vec<_>>();
 
         let generics_impl;
         let (_, ty_generics, where_clause) = input.generics.split_for_impl();
@@ -101,17 +115,38 @@ pub fn expand(input: &DeriveInput, trait_name: &'static str) -> Result<TokenStre
             input.generics.split_for_impl()
         };
 
+        let (error, error_value) = match &error_type {
+            Some(error_type) => (
+                quote!(#error_type<#reference_with_lifetime #input_type#ty_generics>),
+                quote! {
+                    #error_type {
+                        input: value,
+                        expected: &[#(#variant_names),*],
+                        output_type: #output_type,
+                    }
+                },
+            ),
+            None => {
+                let message = format!(
+                    "Only {} can be converted to {}",
+                    variant_names.join(", "),
+                    output_type,
+                );
+                (quote!(&'static str), quote!(#message))
+            }
+        };
+
         let try_from = quote! {
             impl#impl_generics ::core::convert::TryFrom<#reference_with_lifetime #input_type#ty_generics> for
                 (#(#reference_with_lifetime #original_types),*) #where_clause {
-                type Error = &'static str;
+                type Error = #error;
 
                 #[allow(unused_variables)]
                 #[inline]
                 fn try_from(value: #reference_with_lifetime #input_type#ty_generics) -> ::core::result::Result<Self, Self::Error> {
                     match value {
                         #(#matchers)|* => ::core::result::Result::Ok(#vars),
-                        _ => ::core::result::Result::Err(#message),
+                        _ => ::core::result::Result::Err(#error_value),
                     }
                 }
             }
@@ -120,3 +155,71 @@ pub fn expand(input: &DeriveInput, trait_name: &'static str) -> Result<TokenStre
     }
     Ok(tokens)
 }
+
+/// Generates the error type used with `#[try_into(error = "generated")]`.
+///
+/// It's generic over the converted value, so the same type is used for owned
+/// values as well as references.
+fn generated_error(
+    input_type: &syn::Ident,
+    vis: &syn::Visibility,
+    error_type: &syn::Ident,
+) -> TokenStream {
+    let doc = format!(
+        "Error returned when converting a `{}` into the type of one of its variants fails.",
+        input_type,
+    );
+    // The `Error` trait is only available in `no_std` crates via `core::error`.
+    let error_impl = if cfg!(feature = "std") || cfg!(feature = "core-error") {
+        let error = error_module();
+        Some(quote! {
+            impl<T: ::core::fmt::Debug> #error::Error for #error_type<T> {}
+        })
+    } else {
+        None
+    };
+    quote! {
+        #[doc = #doc]
+        #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+        #vis struct #error_type<T> {
+            input: T,
+            expected: &'static [&'static str],
+            output_type: &'static str,
+        }
+
+        impl<T> #error_type<T> {
+            /// Returns a reference to the value that failed to convert.
+            #[inline]
+            #vis fn input(&self) -> &T {
+                &self.input
+            }
+
+            /// Returns the value that failed to convert.
+            #[inline]
+            #vis fn into_input(self) -> T {
+                self.input
+            }
+
+            /// Returns the names of the variants that can be converted.
+            #[inline]
+            #vis fn expected(&self) -> &'static [&'static str] {
+                self.expected
+            }
+        }
+
+        impl<T> ::core::fmt::Display for #error_type<T> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                f.write_str("Only ")?;
+                for (i, name) in self.expected.iter().enumerate() {
+                    if i != 0 {
+                        f.write_str(", ")?;
+                    }
+                    f.write_str(name)?;
+                }
+                write!(f, " can be converted to {}", self.output_type)
+            }
+        }
+
+        #error_impl
+    }
+}
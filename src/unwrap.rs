@@ -1,8 +1,9 @@
-use crate::utils::{AttrParams, DeriveType, State};
-use convert_case::{Case, Casing};
+use crate::utils::{
+    method_ident, variant_method_names, AttrParams, DeriveType, RefType, State,
+};
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
-use syn::{DeriveInput, Fields, Ident, Result};
+use syn::{spanned::Spanned as _, DeriveInput, Error, Fields, Result};
 
 pub fn expand(input: &DeriveInput, trait_name: &'static str) -> Result<TokenStream> {
     let state = State::with_attr_params(
@@ -11,8 +12,8 @@ pub fn expand(input: &DeriveInput, trait_name: &'static str) -> Result<TokenStre
         quote!(),
         String::from("unwrap"),
         AttrParams {
-            enum_: vec!["ignore"],
-            variant: vec!["ignore"],
+            enum_: vec!["ignore", "owned", "ref", "ref_mut", "rename", "vis"],
+            variant: vec!["ignore", "owned", "ref", "ref_mut", "fields", "rename"],
             struct_: vec!["ignore"],
             field: vec!["ignore"],
         },
@@ -25,70 +26,196 @@ pub fn expand(input: &DeriveInput, trait_name: &'static str) -> Result<TokenStre
     let enum_name = &input.ident;
     let (imp_generics, type_generics, where_clause) = input.generics.split_for_impl();
 
+    // The `track-caller` feature is set by our build script based
+    // on rustc version detection, as `#[track_caller]` was
+    // stabilized in a later version (1.46) of Rust than our MSRV (1.36).
+    let track_caller = if cfg!(feature = "track-caller") {
+        quote! { #[track_caller] }
+    } else {
+        quote! {}
+    };
+
+    // Only the part of the method names referring to the variant is renamed,
+    // as each variant gets a whole family of methods.
+    let names = variant_method_names(&state, "{}")?;
+
+    let mut fn_names = vec![];
+    let vis = state.default_info.info.method_vis();
+
     let mut funcs = vec![];
-    for variant_state in state.enabled_variant_data().variant_states {
+    for (variant_state, name) in state
+        .enabled_variant_data()
+        .variant_states
+        .into_iter()
+        .zip(names)
+    {
         let variant = variant_state.variant.unwrap();
-        let fn_name = Ident::new(
-            &format_ident!("unwrap_{}", variant.ident)
-                .to_string()
-                .to_case(Case::Snake),
-            variant.ident.span(),
-        );
         let variant_ident = &variant.ident;
 
-        let (data_pattern, ret_value, ret_type) = match variant.fields {
-            Fields::Named(_) => panic!("cannot unwrap anonymous records"),
+        let selected_fields = &variant_state.default_info.info.fields;
+        let (data_pattern, ret_value, field_types) = match variant.fields {
+            Fields::Named(ref fields) => {
+                let fields: Vec<_> = match selected_fields {
+                    Some(names) => {
+                        let mut selected = vec![];
+                        for name in names {
+                            let field = fields
+                                .named
+                                .iter()
+                                .find(|f| f.ident.as_ref() == Some(name))
+                                .ok_or_else(|| {
+                                    Error::new(
+                                        name.span(),
+                                        format!(
+                                            "Variant `{}` has no field `{}`",
+                                            variant_ident, name,
+                                        ),
+                                    )
+                                })?;
+                            if selected
+                                .iter()
+                                .any(|f: &&syn::Field| f.ident == field.ident)
+                            {
+                                return Err(Error::new(
+                                    name.span(),
+                                    format!(
+                                        "Field `{}` is selected more than once",
+                                        name
+                                    ),
+                                ));
+                            }
+                            selected.push(field);
+                        }
+                        selected
+                    }
+                    None => fields.named.iter().collect(),
+                };
+                let data_pattern: Vec<_> = (0..fields.len())
+                    .map(|n| format_ident!("field_{}", n))
+                    .collect();
+                let field_names = fields.iter().map(|f| &f.ident);
+                let field_types = fields.iter().map(|f| &f.ty).collect();
+                (
+                    quote! { { #(#field_names: #data_pattern,)* .. } },
+                    quote! { (#(#data_pattern),*) },
+                    field_types,
+                )
+            }
+            _ if selected_fields.is_some() => return Err(Error::new(
+                variant.span(),
+                "Only variants with named fields support selecting fields to unwrap",
+            )),
             Fields::Unnamed(ref fields) => {
                 let data_pattern =
                     (0..fields.unnamed.len()).fold(vec![], |mut a, n| {
                         a.push(format_ident!("field_{}", n));
                         a
                     });
-                let ret_type = &fields.unnamed;
+                let field_types: Vec<_> =
+                    fields.unnamed.iter().map(|f| &f.ty).collect();
                 (
                     quote! { (#(#data_pattern),*) },
                     quote! { (#(#data_pattern),*) },
-                    quote! { (#ret_type) },
+                    field_types,
                 )
             }
-            Fields::Unit => (quote! {}, quote! { () }, quote! { () }),
+            Fields::Unit => (quote! {}, quote! { () }, vec![]),
         };
 
-        let other_arms = state.variant_states.iter().map(|variant| {
-            variant.variant.unwrap()
-        }).filter(|variant| {
-            &variant.ident != variant_ident
-        }).map(|variant| {
-            let data_pattern = match variant.fields {
-                Fields::Named(_) => quote! { {..} },
-                Fields::Unnamed(_) => quote! { (..) },
-                Fields::Unit => quote! {},
+        let other_variants: Vec<_> = state
+            .variant_states
+            .iter()
+            .map(|variant| variant.variant.unwrap())
+            .filter(|variant| &variant.ident != variant_ident)
+            .map(|variant| {
+                let data_pattern = match variant.fields {
+                    Fields::Named(_) => quote! { {..} },
+                    Fields::Unnamed(_) => quote! { (..) },
+                    Fields::Unit => quote! {},
+                };
+                let variant_ident = &variant.ident;
+                (
+                    variant_ident,
+                    quote! { #enum_name :: #variant_ident #data_pattern },
+                )
+            })
+            .collect();
+
+        for ref_type in variant_state.default_info.ref_types() {
+            let suffix = match ref_type {
+                RefType::No => "",
+                RefType::Ref => "_ref",
+                RefType::Mut => "_mut",
+            };
+            let snake_name = |prefix: &str| {
+                method_ident(
+                    &format!("{}_{}{}", prefix, name, suffix),
+                    variant_ident.span(),
+                )
             };
-            let variant_ident = &variant.ident;
-            quote! { #enum_name :: #variant_ident #data_pattern =>
-                      panic!(concat!("called `", stringify!(#enum_name), "::", stringify!(#fn_name),
-                                     "()` on a `", stringify!(#variant_ident), "` value"))
+            let fn_name = snake_name("unwrap")?;
+            // The suffixes of methods for references may collide with the
+            // names of other variants.
+            if fn_names.contains(&fn_name) {
+                return Err(Error::new(
+                    variant.span(),
+                    format!(
+                        "Method name `{}` is generated for more than one variant, \
+                         use `#[unwrap(rename = \"...\")]` to rename it",
+                        fn_name,
+                    ),
+                ));
             }
-        });
-
-        // The `track-caller` feature is set by our build script based
-        // on rustc version detection, as `#[track_caller]` was
-        // stabilized in a later version (1.46) of Rust than our MSRV (1.36).
-        let track_caller = if cfg!(feature = "track-caller") {
-            quote! { #[track_caller] }
-        } else {
-            quote! {}
-        };
-        let func = quote! {
-            #track_caller
-            pub fn #fn_name(self) -> #ret_type {
-                match self {
-                    #enum_name ::#variant_ident #data_pattern => #ret_value,
-                    #(#other_arms),*
+            fn_names.push(fn_name.clone());
+            let try_fn_name = snake_name("try_unwrap")?;
+            let expect_fn_name = snake_name("expect")?;
+
+            let reference = ref_type.reference();
+            let self_type = quote! { #reference Self };
+            let ret_type = quote! { (#(#reference #field_types),*) };
+
+            let panic_arms = other_variants.iter().map(|(other_ident, pattern)| {
+                quote! { #pattern =>
+                    panic!(concat!("called `", stringify!(#enum_name), "::", stringify!(#fn_name),
+                                   "()` on a `", stringify!(#other_ident), "` value"))
                 }
-            }
-        };
-        funcs.push(func);
+            });
+            let err_arms = other_variants.iter().map(|(_, pattern)| {
+                quote! { #pattern => ::core::result::Result::Err(self) }
+            });
+            let expect_arms = other_variants.iter().map(|(_, pattern)| {
+                quote! { #pattern => panic!("{}", msg) }
+            });
+
+            funcs.push(quote! {
+                #track_caller
+                #vis fn #fn_name(#reference self) -> #ret_type {
+                    match self {
+                        #enum_name ::#variant_ident #data_pattern => #ret_value,
+                        #(#panic_arms),*
+                    }
+                }
+
+                #vis fn #try_fn_name(
+                    #reference self,
+                ) -> ::core::result::Result<#ret_type, #self_type> {
+                    match self {
+                        #enum_name ::#variant_ident #data_pattern => {
+                            ::core::result::Result::Ok(#ret_value)
+                        }
+                        #(#err_arms),*
+                    }
+                }
+
+                #track_caller
+                #vis fn #expect_fn_name(#reference self, msg: &str) -> #ret_type {
+                    match self {
+                        #enum_name ::#variant_ident #data_pattern => #ret_value,
+                        #(#expect_arms),*
+                    }
+                }
+            });
+        }
     }
 
     let imp = quote! {
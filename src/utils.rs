@@ -104,16 +104,6 @@ pub fn field_idents<'a>(fields: &'a [&'a Field]) -> Vec<&'a Ident> {
         .collect()
 }
 
-pub fn get_field_types_iter<'a>(
-    fields: &'a [&'a Field],
-) -> Box<dyn Iterator<Item = &'a Type> + 'a> {
-    Box::new(fields.iter().map(|f| &f.ty))
-}
-
-pub fn get_field_types<'a>(fields: &'a [&'a Field]) -> Vec<&'a Type> {
-    get_field_types_iter(fields).collect()
-}
-
 pub fn add_extra_type_param_bound_op_output<'a>(
     generics: &'a Generics,
     trait_ident: &'a Ident,
@@ -770,7 +760,7 @@ impl<'input> State<'input> {
             .collect()
     }
 
-    fn enabled_fields_indexes(&self) -> Vec<usize> {
+    pub fn enabled_fields_indexes(&self) -> Vec<usize> {
         self.full_meta_infos
             .iter()
             .map(|info| info.enabled)
@@ -940,9 +930,26 @@ fn get_meta_info(
 
     parse_punctuated_nested_meta(&mut info, &list.nested, allowed_attr_params, None)?;
 
+    // Parameters that only configure how a variant or field is handled don't
+    // explicitly enable it, so they don't cause the others to be skipped.
+    let only_configures = list.nested.iter().all(|meta| match meta {
+        NestedMeta::Meta(meta) => CONFIGURING_ATTR_PARAMS
+            .iter()
+            .any(|param| meta.path().is_ident(param)),
+        NestedMeta::Lit(_) => false,
+    });
+    if !list.nested.is_empty() && only_configures {
+        info.enabled = None;
+    }
+
     Ok(info)
 }
 
+/// Attribute parameters that don't affect whether a variant or field is
+/// enabled.
+const CONFIGURING_ATTR_PARAMS: &[&str] =
+    &["rename", "fields", "vis", "default", "value"];
+
 fn parse_punctuated_nested_meta(
     info: &mut MetaInfo,
     meta: &Punctuated<NestedMeta, Token![,]>,
@@ -998,6 +1005,27 @@ fn parse_punctuated_nested_meta(
                     (None, "ref") => info.ref_ = Some(true),
                     (None, "ref_mut") => info.ref_mut = Some(true),
 
+                    (None, "fields") => {
+                        parse_nested = false;
+                        let mut fields = vec![];
+                        for meta in &list.nested {
+                            match meta {
+                                NestedMeta::Meta(Meta::Path(path))
+                                    if path.get_ident().is_some() =>
+                                {
+                                    fields.push(path.get_ident().unwrap().clone())
+                                }
+                                _ => {
+                                    return Err(Error::new(
+                                        meta.span(),
+                                        "Attribute expects a list of field names",
+                                    ))
+                                }
+                            }
+                        }
+                        info.fields = Some(fields);
+                    }
+
                     #[cfg(any(feature = "from", feature = "into"))]
                     (None, "types")
                     | (Some("owned"), "types")
@@ -1101,6 +1129,11 @@ fn parse_punctuated_nested_meta(
                     (Some("not"), "source") => info.source = Some(false),
                     (None, "backtrace") => info.backtrace = Some(true),
                     (Some("not"), "backtrace") => info.backtrace = Some(false),
+                    (None, "transparent") => info.transparent = Some(true),
+                    (None, "case_insensitive") => info.case_insensitive = Some(true),
+                    (None, "provide") => info.provide = Some(true),
+                    (None, "from") => info.from = Some(true),
+                    (None, "default") => info.default = Some(true),
                     _ => {
                         return Err(Error::new(
                             path.span(),
@@ -1114,10 +1147,41 @@ fn parse_punctuated_nested_meta(
             }
 
             Meta::NameValue(val) => {
-                return Err(Error::new(
-                    val.span(),
-                    "Attribute doesn't support name-value parameters here",
-                ))
+                let path = &val.path;
+                if !allowed_attr_params.iter().any(|param| path.is_ident(param)) {
+                    return Err(Error::new(
+                        meta.span(),
+                        format!(
+                            "Attribute parameter not supported. \
+                             Supported attribute parameters are: {}",
+                            allowed_attr_params.join(", "),
+                        ),
+                    ));
+                }
+
+                let attr_name = path.get_ident().unwrap().to_string();
+                match (wrapper_name, attr_name.as_str(), &val.lit) {
+                    (None, "rename", syn::Lit::Str(s)) => info.rename = Some(s.value()),
+                    #[cfg(feature = "from_str")]
+                    (None, "pattern", syn::Lit::Str(s)) => {
+                        info.pattern = Some(s.clone())
+                    }
+                    (None, "error", syn::Lit::Str(s)) => info.error = Some(s.value()),
+                    (None, "vis", syn::Lit::Str(s)) => {
+                        s.parse::<syn::Visibility>()?;
+                        info.vis = Some(s.value())
+                    }
+                    (None, "value", syn::Lit::Str(s)) => info.value = Some(s.parse()?),
+                    _ => {
+                        return Err(Error::new(
+                            val.span(),
+                            format!(
+                            "Attribute doesn't support name-value parameter `{}` here",
+                            quote! { #path }
+                        ),
+                        ))
+                    }
+                }
             }
         }
     }
@@ -1144,11 +1208,32 @@ pub struct MetaInfo {
     pub ref_mut: Option<bool>,
     pub source: Option<bool>,
     pub backtrace: Option<bool>,
+    pub transparent: Option<bool>,
+    pub provide: Option<bool>,
+    pub case_insensitive: Option<bool>,
+    pub from: Option<bool>,
+    pub rename: Option<String>,
+    #[cfg(feature = "from_str")]
+    pub pattern: Option<syn::LitStr>,
+    pub error: Option<String>,
+    pub fields: Option<Vec<Ident>>,
+    pub vis: Option<String>,
+    pub default: Option<bool>,
+    pub value: Option<TokenStream>,
     #[cfg(any(feature = "from", feature = "into"))]
     pub types: HashMap<RefType, HashSet<syn::Type>>,
 }
 
 impl MetaInfo {
+    /// Returns the visibility of generated inherent methods, which is `pub`
+    /// unless specified otherwise with the `vis` parameter.
+    pub fn method_vis(&self) -> TokenStream {
+        self.vis.as_ref().map_or_else(
+            || quote!(pub),
+            |vis| vis.parse().expect("`vis` is checked while parsing"),
+        )
+    }
+
     fn into_full(self, defaults: FullMetaInfo) -> FullMetaInfo {
         FullMetaInfo {
             enabled: self.enabled.unwrap_or(defaults.enabled),
@@ -1242,3 +1327,98 @@ pub fn is_type_parameter_used_in_type(
         _ => false,
     }
 }
+
+/// Returns the names of the methods generated for the enabled variants of an
+/// enum by derives like `IsVariant` and `Unwrap`.
+///
+/// A `rename` parameter on a variant, or otherwise on the enum, replaces the
+/// `default_template`. Any `{}` in the template is replaced with the snake case
+/// name of the variant.
+#[cfg(any(feature = "constructor", feature = "is_variant", feature = "unwrap"))]
+pub fn variant_method_names(
+    state: &State,
+    default_template: &str,
+) -> Result<Vec<String>> {
+    use convert_case::{Case, Casing};
+
+    let enum_template = state.default_info.info.rename.as_ref();
+    let mut names: Vec<String> = vec![];
+    for variant_state in state.enabled_variant_data().variant_states {
+        let variant = variant_state.variant.unwrap();
+        let template = variant_state
+            .default_info
+            .info
+            .rename
+            .as_ref()
+            .or(enum_template)
+            .map_or(default_template, String::as_str);
+        let name =
+            template.replace("{}", &variant.ident.to_string().to_case(Case::Snake));
+
+        if names.contains(&name) {
+            return Err(Error::new(
+                variant.span(),
+                format!(
+                    "Method name `{}` is generated for more than one variant, \
+                     use `#[{}(rename = \"...\")]` to rename it",
+                    name, state.trait_attr,
+                ),
+            ));
+        }
+        names.push(name);
+    }
+    Ok(names)
+}
+
+/// Returns the module containing the `Error` trait.
+///
+/// The `core-error` feature is set by our build script based on rustc version
+/// detection, as `core::error::Error` is only available since Rust 1.81. It's
+/// preferred, as it's the same trait as `std::error::Error`, but also available
+/// in `no_std` crates.
+#[cfg(any(feature = "display", feature = "error", feature = "try_into"))]
+pub fn error_module() -> TokenStream {
+    if cfg!(feature = "core-error") {
+        quote!(::core::error)
+    } else {
+        quote!(::std::error)
+    }
+}
+
+/// Creates the identifier of a generated method, or an error if `name` isn't a
+/// valid one.
+#[cfg(any(feature = "constructor", feature = "is_variant", feature = "unwrap"))]
+pub fn method_ident(name: &str, span: Span) -> Result<Ident> {
+    if syn::parse_str::<Ident>(name).is_err() {
+        return Err(Error::new(
+            span,
+            format!("`{}` is not a valid method name", name),
+        ));
+    }
+    Ok(Ident::new(name, span))
+}
+
+/// Returns the single type argument of `ty`, if it's a path ending with one
+/// of the given names, e.g. `T` of `Option<T>`.
+#[cfg(any(feature = "display", feature = "error"))]
+pub fn get_single_type_argument<'a>(
+    ty: &'a syn::Type,
+    names: &[&str],
+) -> Option<&'a syn::Type> {
+    let ty = match ty {
+        syn::Type::Path(ty) if ty.qself.is_none() => ty,
+        _ => return None,
+    };
+    let segment = ty.path.segments.last()?;
+    if !names.iter().any(|name| segment.ident == name) {
+        return None;
+    }
+    let args = match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) if args.args.len() == 1 => &args.args,
+        _ => return None,
+    };
+    match &args[0] {
+        syn::GenericArgument::Type(ty) => Some(ty),
+        _ => None,
+    }
+}
@@ -0,0 +1,60 @@
+#![allow(dead_code)]
+
+#[macro_use]
+extern crate derive_more;
+
+#[derive(AsVariant)]
+enum Maybe<T> {
+    Nothing,
+    Just(T),
+}
+
+#[derive(AsVariant)]
+enum Shape {
+    Circle(u32),
+    Rect(u32, u32),
+    Polygon {
+        sides: u8,
+        length: u32,
+    },
+    #[as_variant(ignore)]
+    Empty,
+}
+
+#[derive(AsVariant)]
+enum Nonsense<'a, T> {
+    Ref(&'a T),
+    NoRef,
+}
+
+#[test]
+pub fn test_as_variant() {
+    assert_eq!(Maybe::Just(1).as_just(), Some(&1));
+    assert_eq!(Maybe::<u8>::Nothing.as_just(), None);
+    assert_eq!(Maybe::<u8>::Nothing.as_nothing(), Some(()));
+
+    let shape = Shape::Rect(1, 2);
+    assert_eq!(shape.as_rect(), Some((&1, &2)));
+    assert_eq!(shape.as_circle(), None);
+
+    let shape = Shape::Polygon {
+        sides: 6,
+        length: 3,
+    };
+    assert_eq!(shape.as_polygon(), Some((&6, &3)));
+}
+
+#[test]
+pub fn test_as_variant_mut() {
+    let mut maybe = Maybe::Just(1);
+    *maybe.as_just_mut().unwrap() = 2;
+    assert_eq!(maybe.as_just(), Some(&2));
+
+    let mut shape = Shape::Polygon {
+        sides: 6,
+        length: 3,
+    };
+    *shape.as_polygon_mut().unwrap().1 = 5;
+    assert_eq!(shape.as_polygon(), Some((&6, &5)));
+    assert_eq!(shape.as_rect_mut(), None);
+}
@@ -19,3 +19,86 @@ struct Point2D {
     x: i32,
     y: i32,
 }
+
+mod private {
+    #[derive(Constructor)]
+    #[constructor(vis = "pub(crate)")]
+    pub(crate) struct Crate(pub(crate) i32);
+
+    #[derive(Constructor)]
+    #[constructor(vis = "")]
+    pub(crate) struct Inherited {
+        pub(crate) x: i32,
+    }
+
+    pub(crate) fn inherited(x: i32) -> Inherited {
+        Inherited::new(x)
+    }
+}
+
+#[test]
+fn constructor_vis() {
+    assert_eq!(private::Crate::new(1).0, 1);
+    assert_eq!(private::inherited(2).x, 2);
+}
+
+#[derive(Constructor)]
+struct Counted<T> {
+    name: String,
+    #[new(default)]
+    count: usize,
+    #[new(value = "vec![1, 2]")]
+    cache: Vec<u8>,
+    marker: std::marker::PhantomData<T>,
+}
+
+#[derive(Constructor)]
+struct Tagged<T>(
+    #[new(value = "\"tag\"")] &'static str,
+    T,
+    core::marker::PhantomData<T>,
+);
+
+#[test]
+fn constructor_skipped_fields() {
+    let counted = Counted::<u8>::new("foo".to_string());
+    assert_eq!(counted.name, "foo");
+    assert_eq!(counted.count, 0);
+    assert_eq!(counted.cache, [1, 2]);
+
+    let tagged = Tagged::new(1);
+    assert_eq!(tagged.0, "tag");
+    assert_eq!(tagged.1, 1);
+}
+
+#[derive(Debug, PartialEq, Constructor)]
+enum Shape<T> {
+    Circle(u32),
+    Rect {
+        width: u32,
+        #[new(default)]
+        height: u32,
+    },
+    #[constructor(rename = "new_http")]
+    HTTPRequest(T, std::marker::PhantomData<T>),
+    Empty,
+    #[constructor(ignore)]
+    Ignored,
+}
+
+#[test]
+fn enum_constructors() {
+    assert_eq!(Shape::<u8>::new_circle(1), Shape::Circle(1));
+    assert_eq!(
+        Shape::<u8>::new_rect(2),
+        Shape::Rect {
+            width: 2,
+            height: 0
+        }
+    );
+    assert_eq!(
+        Shape::new_http(3),
+        Shape::HTTPRequest(3, std::marker::PhantomData)
+    );
+    assert_eq!(Shape::<u8>::new_empty(), Shape::Empty);
+}
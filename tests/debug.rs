@@ -0,0 +1,130 @@
+#![allow(dead_code)]
+#[macro_use]
+extern crate derive_more;
+
+#[derive(Debug)]
+struct Unit;
+
+#[derive(Debug)]
+struct Tuple(i32, &'static str);
+
+#[derive(Debug)]
+struct Empty {}
+
+#[derive(Debug)]
+struct Credentials {
+    user: &'static str,
+    #[debug("<redacted>")]
+    password: &'static str,
+    #[debug(fmt = "{:#x}")]
+    flags: u32,
+    #[debug(skip)]
+    handle: *const u8,
+}
+
+#[derive(Debug)]
+enum Message {
+    Quit,
+    Write(&'static str, #[debug(skip)] usize),
+    Move {
+        x: i32,
+        #[debug(fmt = "{}px")]
+        y: i32,
+    },
+}
+
+#[derive(Debug)]
+struct Wrapper<T> {
+    inner: T,
+}
+
+#[derive(Debug)]
+struct Hex<T>(#[debug(fmt = "{:x}")] T);
+
+struct NotDebug;
+
+#[derive(Debug)]
+struct Skipped<T>(#[debug(skip)] T, i32);
+
+#[derive(Debug)]
+struct Raw {
+    r#type: u8,
+}
+
+#[derive(Debug)]
+#[repr(packed)]
+struct Packed {
+    a: u8,
+    b: u32,
+    #[debug(fmt = "{:#x}")]
+    c: u16,
+}
+
+#[derive(Debug)]
+#[repr(C, packed(2))]
+struct PackedTuple(u8, u64);
+
+#[test]
+fn unit() {
+    assert_eq!(format!("{:?}", Unit), "Unit");
+    assert_eq!(format!("{:?}", Message::Quit), "Quit");
+}
+
+#[test]
+fn tuple() {
+    assert_eq!(format!("{:?}", Tuple(1, "a")), r#"Tuple(1, "a")"#);
+    assert_eq!(format!("{:?}", Message::Write("hi", 2)), r#"Write("hi")"#);
+}
+
+#[test]
+fn named() {
+    assert_eq!(format!("{:?}", Empty {}), "Empty");
+    assert_eq!(
+        format!(
+            "{:?}",
+            Credentials {
+                user: "root",
+                password: "hunter2",
+                flags: 255,
+                handle: std::ptr::null(),
+            }
+        ),
+        r#"Credentials { user: "root", password: <redacted>, flags: 0xff }"#
+    );
+    assert_eq!(
+        format!("{:?}", Message::Move { x: 1, y: 2 }),
+        "Move { x: 1, y: 2px }"
+    );
+    assert_eq!(format!("{:?}", Raw { r#type: 1 }), "Raw { type: 1 }");
+}
+
+#[test]
+fn pretty() {
+    assert_eq!(
+        format!("{:#?}", Message::Move { x: 1, y: 2 }),
+        "Move {\n    x: 1,\n    y: 2px,\n}"
+    );
+    assert_eq!(
+        format!("{:#?}", Tuple(1, "a")),
+        "Tuple(\n    1,\n    \"a\",\n)"
+    );
+}
+
+#[test]
+fn generic() {
+    assert_eq!(
+        format!("{:?}", Wrapper { inner: Some(1) }),
+        "Wrapper { inner: Some(1) }"
+    );
+    assert_eq!(format!("{:?}", Hex(255)), "Hex(ff)");
+    assert_eq!(format!("{:?}", Skipped(NotDebug, 1)), "Skipped(1)");
+}
+
+#[test]
+fn packed() {
+    assert_eq!(
+        format!("{:?}", Packed { a: 1, b: 2, c: 255 }),
+        "Packed { a: 1, b: 2, c: 0xff }",
+    );
+    assert_eq!(format!("{:?}", PackedTuple(1, 2)), "PackedTuple(1, 2)");
+}
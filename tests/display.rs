@@ -120,6 +120,251 @@ enum Affix {
 #[display(fmt = "{:?}", self)]
 struct DebugStructAsDisplay;
 
+#[derive(Display)]
+#[display("{name} is {age}")]
+struct Person {
+    name: &'static str,
+    age: u8,
+}
+
+#[derive(Display)]
+#[display("{_1}, {_0}")]
+struct Swapped(i32, &'static str);
+
+#[derive(Display)]
+enum Shape {
+    #[display("circle of radius {radius:.1}")]
+    Circle { radius: f64 },
+    #[display("{_0}x{_1} {unit}", "unit = \"cm\"")]
+    Rect(u32, u32),
+    #[display(fmt = "{} {x}", "self.name()")]
+    Point { x: i32 },
+}
+
+impl Shape {
+    fn name(&self) -> &str {
+        "point"
+    }
+}
+
+#[derive(Display)]
+enum Measure {
+    #[display(fmt = "{:>width$}|{:.*}", value, "2", value)]
+    Aligned { value: f64, width: usize },
+    #[display(fmt = "{0:.prec$} {0:e}", _0, "prec = 1")]
+    Scientific(f64),
+}
+
+#[derive(Display)]
+enum TableCell {
+    #[display(fmt = "<{}>", _0, pad)]
+    Int(i32),
+    #[display("{_0}..{_1}", pad)]
+    Range(u8, u8),
+    #[display(fmt = "{}", "\"x\".repeat(*_0)", pad)]
+    Long(usize),
+    Plain(i32),
+}
+
+#[test]
+fn check_display_pad() {
+    assert_eq!(format!("{:>6}", TableCell::Int(7)), "   <7>");
+    assert_eq!(format!("{:<6}|", TableCell::Int(7)), "<7>   |");
+    assert_eq!(format!("{:*^7}", TableCell::Int(7)), "**<7>**");
+    assert_eq!(format!("{:.3}", TableCell::Int(1234)), "<12");
+    assert_eq!(format!("{:6}|", TableCell::Range(1, 9)), "1..9  |");
+    assert_eq!(format!("{:>6}", TableCell::Plain(7)), "     7");
+
+    // Outputs not fitting into the stack buffer are formatted twice.
+    let long = "x".repeat(300);
+    assert_eq!(format!("{}", TableCell::Long(300)), long);
+    assert_eq!(
+        format!("{:-^304}", TableCell::Long(300)),
+        format!("--{}--", long)
+    );
+    assert_eq!(format!("{:>3.2}", TableCell::Long(300)), " xx");
+}
+
+#[derive(Display)]
+#[display(fmt = "[{_type}] {}")]
+enum Event {
+    #[display("{_variant}(addr={_0})")]
+    ConnectionRefused(&'static str),
+    #[display(fmt = "{}: {}", _variant, "code")]
+    Closed {
+        code: u16,
+    },
+    Timeout,
+}
+
+#[derive(Display)]
+#[display("{_variant}")]
+enum Level {
+    Info,
+    Warning,
+}
+
+#[derive(Display)]
+#[display(fmt = "{} #{}", "_type.to_lowercase()", _0)]
+struct Ticket(u32);
+
+#[test]
+fn check_display_builtins() {
+    assert_eq!(
+        Event::ConnectionRefused("127.0.0.1").to_string(),
+        "[Event] ConnectionRefused(addr=127.0.0.1)"
+    );
+    assert_eq!(
+        Event::Closed { code: 1000 }.to_string(),
+        "[Event] Closed: 1000"
+    );
+    assert_eq!(Event::Timeout.to_string(), "[Event] Timeout");
+    assert_eq!(Level::Info.to_string(), "Info");
+    assert_eq!(Level::Warning.to_string(), "Warning");
+    assert_eq!(Ticket(7).to_string(), "ticket #7");
+}
+
+#[derive(Display)]
+#[display(rename_all = "snake_case")]
+enum HttpStatus {
+    HttpError,
+    #[display(rename = "ok!")]
+    Ok,
+    #[display(fmt = "moved to {}", _0)]
+    MovedPermanently(&'static str),
+    #[display("{_variant}")]
+    NotFound,
+}
+
+#[derive(Display)]
+#[display(rename_all = "SCREAMING_SNAKE_CASE")]
+#[display(fmt = "<{}>")]
+enum Screaming {
+    HttpError,
+}
+
+#[derive(Display)]
+#[display(rename_all = "kebab-case")]
+enum Kebab {
+    HttpError,
+}
+
+#[derive(Display)]
+#[display(rename_all = "lowercase")]
+enum Lower {
+    HttpError,
+}
+
+#[test]
+fn check_display_rename() {
+    assert_eq!(HttpStatus::HttpError.to_string(), "http_error");
+    assert_eq!(HttpStatus::Ok.to_string(), "ok!");
+    assert_eq!(HttpStatus::MovedPermanently("/").to_string(), "moved to /");
+    assert_eq!(HttpStatus::NotFound.to_string(), "not_found");
+    assert_eq!(Screaming::HttpError.to_string(), "<HTTP_ERROR>");
+    assert_eq!(Kebab::HttpError.to_string(), "http-error");
+    assert_eq!(Lower::HttpError.to_string(), "httperror");
+}
+
+mod with {
+    use std::{fmt, net::Ipv4Addr};
+
+    fn hex(value: &u32, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:#x}", value)
+    }
+
+    fn octets(addr: &Ipv4Addr, f: &mut fmt::Formatter) -> fmt::Result {
+        let [a, b, c, d] = addr.octets();
+        write!(f, "{}-{}-{}-{}", a, b, c, d)
+    }
+
+    fn bits(value: &Bits, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:#b}", unsafe { value.byte })
+    }
+
+    fn state(value: &State, f: &mut fmt::Formatter) -> fmt::Result {
+        match value {
+            State::On => f.write_str("on"),
+            State::Off(n) => write!(f, "off for {}s", n),
+        }
+    }
+
+    fn len<T>(value: &[T], f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} items", value.len())
+    }
+
+    #[derive(Display)]
+    #[display(with = "bits")]
+    union Bits {
+        byte: u8,
+    }
+
+    #[derive(Display)]
+    #[display(with = "state")]
+    enum State {
+        On,
+        Off(u32),
+    }
+
+    #[derive(Display)]
+    enum Event {
+        #[display(with = "Event::describe")]
+        Started,
+        Connected(#[display(with = "octets")] Ipv4Addr),
+        #[display(fmt = "{} at {}", id, addr)]
+        Moved {
+            #[display(with = "hex")]
+            id: u32,
+            #[display(with = "octets")]
+            addr: Ipv4Addr,
+        },
+    }
+
+    impl Event {
+        fn describe(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("started")
+        }
+    }
+
+    #[derive(Display)]
+    #[display("{items}")]
+    struct Queue<T> {
+        #[display(with = "len")]
+        items: Vec<T>,
+    }
+
+    struct NotDisplay;
+
+    #[test]
+    fn type_level() {
+        assert_eq!(Bits { byte: 5 }.to_string(), "0b101");
+        assert_eq!(State::On.to_string(), "on");
+        assert_eq!(State::Off(3).to_string(), "off for 3s");
+    }
+
+    #[test]
+    fn variant_level() {
+        assert_eq!(Event::Started.to_string(), "started");
+    }
+
+    #[test]
+    fn field_level() {
+        let addr = Ipv4Addr::new(10, 0, 0, 1);
+        assert_eq!(Event::Connected(addr).to_string(), "10-0-0-1");
+        assert_eq!(
+            Event::Moved { id: 255, addr }.to_string(),
+            "0xff at 10-0-0-1"
+        );
+        assert_eq!(
+            Queue {
+                items: vec![NotDisplay, NotDisplay],
+            }
+            .to_string(),
+            "2 items"
+        );
+    }
+}
+
 #[test]
 fn check_display() {
     assert_eq!(MyInt(-2).to_string(), "-2");
@@ -160,6 +405,38 @@ fn check_display() {
     assert_eq!(DebugStructAsDisplay.to_string(), "DebugStructAsDisplay");
 }
 
+#[test]
+fn check_display_field_names() {
+    assert_eq!(
+        Person {
+            name: "Alice",
+            age: 30,
+        }
+        .to_string(),
+        "Alice is 30"
+    );
+    assert_eq!(Swapped(1, "one").to_string(), "one, 1");
+    assert_eq!(
+        Shape::Circle { radius: 2.0 }.to_string(),
+        "circle of radius 2.0"
+    );
+    assert_eq!(Shape::Rect(3, 4).to_string(), "3x4 cm");
+    assert_eq!(Shape::Point { x: 5 }.to_string(), "point 5");
+}
+
+#[test]
+fn check_display_width_and_precision_args() {
+    assert_eq!(
+        Measure::Aligned {
+            value: 1.5,
+            width: 5,
+        }
+        .to_string(),
+        "  1.5|1.50"
+    );
+    assert_eq!(Measure::Scientific(1250.0).to_string(), "1250.0 1.25e3");
+}
+
 mod generic {
     #[derive(Display)]
     #[display(fmt = "Generic {}", field)]
@@ -240,6 +517,47 @@ mod generic {
         assert_eq!(s.to_string(), "8 255 {} 10 0xff -    8  FF ");
     }
 
+    #[derive(Display)]
+    #[display("{value:x} {value:?}")]
+    struct NamedPlaceholderGenericStruct<T> {
+        value: T,
+    }
+    #[test]
+    fn named_placeholder_generic_struct() {
+        let s = NamedPlaceholderGenericStruct { value: 255 };
+        assert_eq!(s.to_string(), "ff 255");
+    }
+
+    #[derive(Display)]
+    #[display(fmt = "{hex:#X} {:o}", "&self.b", "hex = *a")]
+    struct ExprArgsGenericStruct<'a, A, B> {
+        a: &'a A,
+        b: B,
+    }
+    #[test]
+    fn expr_args_generic_struct() {
+        let s = ExprArgsGenericStruct { a: &255, b: 8 };
+        assert_eq!(s.to_string(), "0xFF 10");
+    }
+
+    #[derive(Display)]
+    enum WidthPrecisionGenericEnum<A, B> {
+        #[display(fmt = "{:>1$e}|{:.*}", _0, _1, _0)]
+        A(A, usize),
+        #[display("{value:.prec$} {:>width$?}", value, "width = 6")]
+        B { value: B, prec: usize },
+    }
+    #[test]
+    fn width_precision_generic_enum() {
+        let a = WidthPrecisionGenericEnum::A::<_, f32>(1.5, 6);
+        assert_eq!(a.to_string(), " 1.5e0|1.500000");
+        let b = WidthPrecisionGenericEnum::B::<f32, _> {
+            value: 1.25,
+            prec: 1,
+        };
+        assert_eq!(b.to_string(), "1.2   1.25");
+    }
+
     #[derive(Display)]
     #[display(fmt = "{}", "3 * 4")]
     struct UnusedGenericStruct<T>(T);
@@ -427,3 +745,149 @@ mod generic {
         }
     }
 }
+
+mod alt {
+    #[derive(Display)]
+    enum Status {
+        #[display(fmt = "{} failed", job, alt = "{} failed\n  code: {}", job, code)]
+        Failed { job: &'static str, code: i32 },
+        #[display("{_0}", alt = "{_0} (after {_1}s)")]
+        Done(&'static str, u32),
+        #[display(fmt = "idle", alt = "idle for {:.*}s", "1", _0)]
+        Idle(f32),
+    }
+
+    #[derive(Display)]
+    #[display(fmt = "{}", value, pad, alt = "{value:#x}")]
+    struct Generic<T> {
+        value: T,
+    }
+
+    #[test]
+    fn picks_alt_format() {
+        let failed = Status::Failed {
+            job: "build",
+            code: 2,
+        };
+        assert_eq!(failed.to_string(), "build failed");
+        assert_eq!(format!("{:#}", failed), "build failed\n  code: 2");
+        assert_eq!(Status::Done("test", 3).to_string(), "test");
+        assert_eq!(format!("{:#}", Status::Done("test", 3)), "test (after 3s)");
+        assert_eq!(Status::Idle(1.25).to_string(), "idle");
+        assert_eq!(format!("{:#}", Status::Idle(1.25)), "idle for 1.2s");
+    }
+
+    #[test]
+    fn generic_alt_format() {
+        assert_eq!(format!("{:>4}", Generic { value: 10 }), "  10");
+        assert_eq!(format!("{:>#5}", Generic { value: 10 }), "  0xa");
+    }
+}
+
+#[cfg(feature = "error")]
+mod chain {
+    #[derive(Debug, Display, Error)]
+    #[display(fmt = "connection refused")]
+    struct Refused;
+
+    #[derive(Debug, Display, Error)]
+    enum ConnectError {
+        #[display(fmt = "cannot connect to {}", addr, chain)]
+        Io { addr: &'static str, source: Refused },
+        #[display("timeout after {_1}s", chain)]
+        Timeout(#[error(source)] Refused, u32),
+        #[display(fmt = "retry failed", chain)]
+        Retry(Refused),
+    }
+
+    #[derive(Debug, Display, Error)]
+    #[display(fmt = "request failed", chain, pad)]
+    struct RequestError {
+        source: ConnectError,
+    }
+
+    #[derive(Debug, Display, Error)]
+    enum AppError {
+        #[error(transparent)]
+        Connect(ConnectError),
+        #[display(fmt = "app failed", chain)]
+        Other { source: Refused },
+    }
+
+    #[derive(Debug, Display, Error)]
+    #[error(transparent)]
+    struct Opaque(AppError);
+
+    #[derive(Debug, Display, Error)]
+    #[display(fmt = "query failed", chain)]
+    struct QueryError<E> {
+        source: Option<E>,
+    }
+
+    #[derive(Debug, Display)]
+    #[display("{context}", chain)]
+    struct Generic<E> {
+        context: &'static str,
+        source: E,
+    }
+
+    #[test]
+    fn appends_source() {
+        assert_eq!(
+            ConnectError::Io {
+                addr: "localhost",
+                source: Refused,
+            }
+            .to_string(),
+            "cannot connect to localhost: connection refused"
+        );
+        assert_eq!(
+            ConnectError::Timeout(Refused, 5).to_string(),
+            "timeout after 5s: connection refused"
+        );
+        assert_eq!(
+            ConnectError::Retry(Refused).to_string(),
+            "retry failed: connection refused"
+        );
+    }
+
+    #[test]
+    fn appends_nested_sources() {
+        let err = RequestError {
+            source: ConnectError::Retry(Refused),
+        };
+        assert_eq!(format!("{:.21}", err), "request failed: retry");
+        assert_eq!(
+            err.to_string(),
+            "request failed: retry failed: connection refused"
+        );
+        let generic = Generic {
+            context: "loading",
+            source: 42,
+        };
+        assert_eq!(generic.to_string(), "loading: 42");
+    }
+
+    #[test]
+    fn appends_optional_source() {
+        let err = QueryError {
+            source: Some(Refused),
+        };
+        assert_eq!(err.to_string(), "query failed: connection refused");
+        let err = QueryError::<Refused> { source: None };
+        assert_eq!(err.to_string(), "query failed");
+    }
+
+    #[test]
+    fn transparent() {
+        use std::error::Error as _;
+
+        let err = Opaque(AppError::Connect(ConnectError::Retry(Refused)));
+        assert_eq!(err.to_string(), "retry failed: connection refused");
+        assert!(err.source().unwrap().is::<Refused>());
+        assert_eq!(
+            format!("{:>10}", Opaque(AppError::Other { source: Refused })),
+            "app failed: connection refused"
+        );
+    }
+}
@@ -68,8 +68,24 @@ enum TestErr {
     },
     #[error(ignore)]
     UnnamedVariantIgnoreRedundant(i32, i32),
+    #[error(transparent)]
+    NamedTransparent {
+        inner: WrappedErr,
+    },
+    #[error(transparent)]
+    UnnamedTransparent(WrappedErr),
+    #[error(transparent)]
+    UnnamedTransparentNoSource(SimpleErr),
+    NamedImplicitOptionalSource {
+        source: Option<SimpleErr>,
+    },
+    UnnamedImplicitBoxedSource(Box<dyn Error + Send + Sync>),
 }
 
+derive_display!(WrappedErr);
+#[derive(Debug, Error)]
+struct WrappedErr(SimpleErr);
+
 #[test]
 fn unit() {
     assert!(TestErr::Unit.source().is_none());
@@ -247,3 +263,75 @@ fn unnamed_variant_ignore_redundant() {
 
     assert!(err.source().is_none())
 }
+
+#[test]
+fn named_variant_transparent() {
+    let err = TestErr::NamedTransparent {
+        inner: WrappedErr(SimpleErr),
+    };
+
+    assert!(err.source().is_some());
+    assert!(err.source().unwrap().is::<SimpleErr>());
+}
+
+#[test]
+fn unnamed_variant_transparent() {
+    let err = TestErr::UnnamedTransparent(WrappedErr(SimpleErr));
+
+    assert!(err.source().is_some());
+    assert!(err.source().unwrap().is::<SimpleErr>());
+}
+
+#[test]
+fn unnamed_variant_transparent_no_source() {
+    let err = TestErr::UnnamedTransparentNoSource(SimpleErr);
+
+    assert!(err.source().is_none());
+}
+
+#[test]
+fn named_implicit_optional_source() {
+    let err = TestErr::NamedImplicitOptionalSource { source: None };
+
+    assert!(err.source().is_none());
+
+    let err = TestErr::NamedImplicitOptionalSource {
+        source: Some(SimpleErr),
+    };
+
+    assert!(err.source().is_some());
+    assert!(err.source().unwrap().is::<SimpleErr>());
+}
+
+#[test]
+fn unnamed_implicit_boxed_source() {
+    let err = TestErr::UnnamedImplicitBoxedSource(Box::new(SimpleErr));
+
+    assert!(err.source().is_some());
+    assert!(err.source().unwrap().is::<SimpleErr>());
+}
+
+#[test]
+fn variants_from_source() {
+    derive_display!(TestErr);
+    #[derive(Debug, Error)]
+    enum TestErr {
+        #[error(from)]
+        Named {
+            source: SimpleErr,
+            context: String,
+        },
+        Unnamed(#[error(from)] WrappedErr),
+        WithoutFrom(Box<dyn Error>),
+    }
+
+    let err = TestErr::from(SimpleErr);
+    match &err {
+        TestErr::Named { context, .. } => assert_eq!(context, ""),
+        _ => panic!("expected `TestErr::Named`"),
+    }
+    assert!(err.source().unwrap().is::<SimpleErr>());
+
+    let err = TestErr::from(WrappedErr(SimpleErr));
+    assert!(err.source().unwrap().is::<WrappedErr>());
+}
@@ -243,3 +243,52 @@ fn unnamed_struct_ignore_redundant() {
 
     assert!(TestErr::<i32>::default().source().is_none())
 }
+
+#[test]
+fn unnamed_struct_transparent() {
+    derive_display!(WrappedErr);
+    #[derive(Default, Debug, Error)]
+    struct WrappedErr(SimpleErr);
+
+    derive_display!(TestErr, E);
+    #[derive(Default, Debug, Error)]
+    #[error(transparent)]
+    struct TestErr<E>(E);
+
+    let err = TestErr::<WrappedErr>::default();
+    assert!(err.source().is_some());
+    assert!(err.source().unwrap().is::<SimpleErr>());
+}
+
+#[test]
+fn named_implicit_optional_source() {
+    derive_display!(TestErr, E);
+    #[derive(Default, Debug, Error)]
+    struct TestErr<E> {
+        source: Option<E>,
+    }
+
+    assert!(TestErr::<SimpleErr>::default().source().is_none());
+
+    let err = TestErr {
+        source: Some(SimpleErr),
+    };
+    assert!(err.source().is_some());
+    assert!(err.source().unwrap().is::<SimpleErr>());
+}
+
+#[test]
+fn named_generic_from_source() {
+    derive_display!(TestErr, E, T);
+    #[derive(Debug, Error)]
+    struct TestErr<E, T> {
+        #[error(from)]
+        source: E,
+        field: T,
+    }
+
+    let err = TestErr::<_, String>::from(SimpleErr);
+    assert_eq!(err.field, "");
+    assert!(err.source().is_some());
+    assert!(err.source().unwrap().is::<SimpleErr>());
+}
@@ -247,3 +247,165 @@ fn unnamed_struct_ignore_redundant() {
 
     assert!(TestErr::default().source().is_none())
 }
+
+derive_display!(WrappedErr);
+#[derive(Default, Debug, Error)]
+struct WrappedErr {
+    source: SimpleErr,
+}
+
+#[test]
+fn named_struct_transparent() {
+    derive_display!(TestErr);
+    #[derive(Default, Debug, Error)]
+    #[error(transparent)]
+    struct TestErr {
+        inner: WrappedErr,
+    }
+
+    let err = TestErr::default();
+    assert!(err.source().is_some());
+    assert!(err.source().unwrap().is::<SimpleErr>());
+}
+
+#[test]
+fn unnamed_struct_transparent() {
+    derive_display!(TestErr);
+    #[derive(Default, Debug, Error)]
+    #[error(transparent)]
+    struct TestErr(WrappedErr);
+
+    let err = TestErr::default();
+    assert!(err.source().is_some());
+    assert!(err.source().unwrap().is::<SimpleErr>());
+}
+
+#[test]
+fn unnamed_struct_transparent_no_source() {
+    derive_display!(TestErr);
+    #[derive(Default, Debug, Error)]
+    #[error(transparent)]
+    struct TestErr(SimpleErr);
+
+    assert!(TestErr::default().source().is_none());
+}
+
+#[test]
+fn named_implicit_optional_source() {
+    derive_display!(TestErr);
+    #[derive(Default, Debug, Error)]
+    struct TestErr {
+        source: Option<SimpleErr>,
+        field: i32,
+    }
+
+    assert!(TestErr::default().source().is_none());
+
+    let err = TestErr {
+        source: Some(SimpleErr),
+        field: 0,
+    };
+    assert!(err.source().is_some());
+    assert!(err.source().unwrap().is::<SimpleErr>());
+}
+
+#[test]
+fn unnamed_implicit_boxed_source() {
+    derive_display!(TestErr);
+    #[derive(Debug, Error)]
+    struct TestErr(Box<dyn Error + Send + Sync>);
+
+    let err = TestErr(Box::new(SimpleErr));
+    assert!(err.source().is_some());
+    assert!(err.source().unwrap().is::<SimpleErr>());
+}
+
+#[test]
+fn named_explicit_optional_boxed_source() {
+    derive_display!(TestErr);
+    #[derive(Default, Debug, Error)]
+    struct TestErr {
+        #[error(source)]
+        cause: Option<Box<dyn Error>>,
+        field: i32,
+    }
+
+    assert!(TestErr::default().source().is_none());
+
+    let err = TestErr {
+        cause: Some(Box::new(SimpleErr)),
+        field: 0,
+    };
+    assert!(err.source().is_some());
+    assert!(err.source().unwrap().is::<SimpleErr>());
+}
+
+#[test]
+fn unnamed_struct_transparent_optional_boxed() {
+    derive_display!(TestErr);
+    #[derive(Default, Debug, Error)]
+    #[error(transparent)]
+    struct TestErr(Option<std::sync::Arc<dyn Error + Send + Sync + 'static>>);
+
+    assert!(TestErr::default().source().is_none());
+
+    let err = TestErr(Some(std::sync::Arc::new(WrappedErr::default())));
+    assert!(err.source().is_some());
+    assert!(err.source().unwrap().is::<SimpleErr>());
+}
+
+#[test]
+fn named_struct_from_source() {
+    derive_display!(TestErr);
+    #[derive(Debug, Error)]
+    struct TestErr {
+        #[error(from)]
+        source: SimpleErr,
+        field: i32,
+    }
+
+    let err = TestErr::from(SimpleErr);
+    assert_eq!(err.field, 0);
+    assert!(err.source().is_some());
+    assert!(err.source().unwrap().is::<SimpleErr>());
+}
+
+#[test]
+fn unnamed_struct_from_transparent() {
+    derive_display!(TestErr);
+    #[derive(Debug, Error)]
+    #[error(transparent, from)]
+    struct TestErr(WrappedErr);
+
+    let err: TestErr = WrappedErr::default().into();
+    assert!(err.source().is_some());
+    assert!(err.source().unwrap().is::<SimpleErr>());
+}
+
+#[test]
+fn named_struct_from_optional_source() {
+    derive_display!(TestErr);
+    #[derive(Debug, Error)]
+    struct TestErr {
+        #[error(from)]
+        source: Option<SimpleErr>,
+        field: i32,
+    }
+
+    let err = TestErr::from(SimpleErr);
+    assert_eq!(err.field, 0);
+    assert!(err.source().is_some());
+    assert!(err.source().unwrap().is::<SimpleErr>());
+}
+
+#[test]
+fn unnamed_struct_from_boxed_source() {
+    derive_display!(TestErr);
+    #[derive(Debug, Error)]
+    #[error(from)]
+    struct TestErr(Box<dyn Error + Send + Sync>);
+
+    let err = TestErr::from(Box::new(SimpleErr) as Box<dyn Error + Send + Sync>);
+    assert!(err.source().is_some());
+    assert!(err.source().unwrap().is::<SimpleErr>());
+}
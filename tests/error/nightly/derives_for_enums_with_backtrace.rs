@@ -270,3 +270,29 @@ fn unnamed_explicit_supresses_implicit() {
     assert_bt!(==, err, .get_stored_backtrace);
     assert_bt!(!=, err, .get_unused_backtrace);
 }
+
+#[test]
+fn source_backtrace_and_provide() {
+    derive_display!(TestErr);
+    #[derive(Debug, Error)]
+    enum TestErr {
+        Source {
+            #[error(backtrace)]
+            source: BacktraceErr,
+        },
+        Provide(#[error(provide)] u16, Backtrace, i32),
+        Unit,
+    }
+
+    let err = TestErr::Source {
+        source: BacktraceErr::default(),
+    };
+    assert!(err.backtrace().is_some());
+    assert!(std::error::request_ref::<u16>(&err).is_none());
+
+    let err = TestErr::Provide(500, Backtrace::force_capture(), 0);
+    assert!(err.backtrace().is_some());
+    assert_eq!(std::error::request_ref::<u16>(&err), Some(&500));
+
+    assert!(TestErr::Unit.backtrace().is_none());
+}
@@ -273,3 +273,34 @@ fn unnamed_explicit_supresses_implicit() {
     assert_bt!(==, err, 0);
     assert_bt!(!=, err, 1);
 }
+
+#[test]
+fn named_source_backtrace() {
+    derive_display!(TestErr, E);
+    #[derive(Default, Debug, Error)]
+    struct TestErr<E> {
+        #[error(backtrace)]
+        source: E,
+    }
+
+    let err = TestErr::<BacktraceErr>::default();
+    assert!(err.backtrace().is_some());
+    assert_eq!(
+        err.backtrace().unwrap().to_string(),
+        err.source.backtrace.to_string(),
+    );
+}
+
+#[test]
+fn named_provide() {
+    derive_display!(TestErr, T);
+    #[derive(Debug, Error)]
+    struct TestErr<T> {
+        #[error(provide)]
+        field: T,
+    }
+
+    let err = TestErr { field: 42_u8 };
+    assert_eq!(std::error::request_ref::<u8>(&err), Some(&42));
+    assert!(err.backtrace().is_none());
+}
@@ -278,3 +278,117 @@ fn unnamed_explicit_supresses_implicit() {
     assert_bt!(==, err, 0);
     assert_bt!(!=, err, 1);
 }
+
+#[test]
+fn named_source_backtrace() {
+    derive_display!(TestErr);
+    #[derive(Default, Debug, Error)]
+    struct TestErr {
+        #[error(backtrace)]
+        source: BacktraceErr,
+        field: i32,
+    }
+
+    let err = TestErr::default();
+    assert!(err.backtrace().is_some());
+    assert_eq!(
+        err.backtrace().unwrap().to_string(),
+        err.source.backtrace.to_string(),
+    );
+}
+
+#[test]
+fn unnamed_source_backtrace() {
+    derive_display!(TestErr);
+    #[derive(Default, Debug, Error)]
+    struct TestErr(#[error(backtrace)] BacktraceErr);
+
+    let err = TestErr::default();
+    assert!(err.backtrace().is_some());
+    assert_eq!(
+        err.backtrace().unwrap().to_string(),
+        err.0.backtrace.to_string(),
+    );
+}
+
+#[test]
+fn named_optional_source_backtrace() {
+    derive_display!(TestErr);
+    #[derive(Default, Debug, Error)]
+    struct TestErr {
+        #[error(backtrace)]
+        source: Option<BacktraceErr>,
+    }
+
+    assert!(TestErr::default().backtrace().is_none());
+
+    let err = TestErr {
+        source: Some(BacktraceErr::default()),
+    };
+    assert!(err.backtrace().is_some());
+}
+
+#[test]
+fn named_provide() {
+    derive_display!(TestErr);
+    #[derive(Debug, Error)]
+    struct TestErr {
+        #[error(provide)]
+        code: u16,
+        #[error(provide)]
+        message: String,
+        field: i32,
+    }
+
+    let err = TestErr {
+        code: 404,
+        message: "not found".to_owned(),
+        field: 0,
+    };
+    assert_eq!(std::error::request_ref::<u16>(&err), Some(&404));
+    assert_eq!(
+        std::error::request_ref::<String>(&err).map(String::as_str),
+        Some("not found"),
+    );
+    assert!(std::error::request_ref::<i32>(&err).is_none());
+    assert!(err.backtrace().is_none());
+}
+
+#[test]
+fn unnamed_provide_with_backtrace() {
+    derive_display!(TestErr);
+    #[derive(Debug, Error)]
+    struct TestErr(#[error(provide)] u16, Backtrace, i32);
+
+    let err = TestErr(500, Backtrace::force_capture(), 0);
+    assert_eq!(std::error::request_ref::<u16>(&err), Some(&500));
+    assert!(err.backtrace().is_some());
+    assert_bt!(==, err, 1);
+}
+
+#[test]
+fn named_from_source_captures_backtrace() {
+    derive_display!(TestErr);
+    #[derive(Debug, Error)]
+    struct TestErr {
+        #[error(from)]
+        source: SimpleErr,
+        backtrace: Backtrace,
+        field: i32,
+    }
+
+    let err = TestErr::from(SimpleErr);
+    assert!(err.backtrace().is_some());
+    assert_bt!(==, err);
+}
+
+#[test]
+fn unnamed_source_backtrace_display_chain() {
+    #[derive(Debug, Display, Error)]
+    #[display("wrapped", chain)]
+    struct TestErr(#[error(backtrace)] BacktraceErr);
+
+    let err = TestErr(BacktraceErr::default());
+    assert!(err.source().is_some());
+    assert_eq!(err.to_string(), "wrapped: ");
+}
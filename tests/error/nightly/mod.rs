@@ -2,7 +2,19 @@ use std::backtrace::Backtrace;
 
 use super::*;
 
-/// Asserts that backtrace returned by `Error::backtrace` method equals/not-equals
+/// Provides access to the backtrace provided via `Error::provide` method,
+/// in the same way as the former `Error::backtrace` method did.
+trait ErrorBacktrace {
+    fn backtrace(&self) -> Option<&Backtrace>;
+}
+
+impl<E: Error + ?Sized> ErrorBacktrace for E {
+    fn backtrace(&self) -> Option<&Backtrace> {
+        std::error::request_ref::<Backtrace>(self)
+    }
+}
+
+/// Asserts that backtrace provided by `Error::provide` method equals/not-equals
 /// backtrace stored in object itself.
 ///
 /// Comparison is done by converting backtraces to strings
@@ -79,7 +91,7 @@ impl Default for BacktraceErr {
 }
 
 impl Error for BacktraceErr {
-    fn backtrace(&self) -> Option<&Backtrace> {
-        Some(&self.backtrace)
+    fn provide<'a>(&'a self, request: &mut std::error::Request<'a>) {
+        request.provide_ref::<Backtrace>(&self.backtrace);
     }
 }
@@ -1,4 +1,4 @@
-#![cfg_attr(feature = "nightly", feature(backtrace))]
+#![cfg_attr(feature = "nightly", feature(error_generic_member_access))]
 
 #[macro_use]
 extern crate derive_more;
@@ -9,3 +9,100 @@ struct MyInt(i32);
 struct Point1D {
     x: i32,
 }
+
+#[derive(FromStr, Debug, Eq, PartialEq)]
+#[from_str(case_insensitive)]
+enum Mode {
+    Fast,
+    Safe,
+    #[from_str(rename = "slow-and-steady")]
+    Slow,
+}
+
+#[derive(FromStr, Debug, Eq, PartialEq)]
+enum CaseSensitive {
+    Case,
+    #[from_str(rename = "CASE")]
+    Upper,
+    Other,
+}
+
+#[derive(FromStr)]
+enum Empty {}
+
+#[test]
+fn enum_case_insensitive() {
+    assert_eq!("Fast".parse::<Mode>(), Ok(Mode::Fast));
+    assert_eq!("fast".parse::<Mode>(), Ok(Mode::Fast));
+    assert_eq!("SAFE".parse::<Mode>(), Ok(Mode::Safe));
+    assert_eq!("Slow-And-Steady".parse::<Mode>(), Ok(Mode::Slow));
+    assert!("Slow".parse::<Mode>().is_err());
+}
+
+#[test]
+fn enum_case_sensitive() {
+    assert_eq!("Case".parse::<CaseSensitive>(), Ok(CaseSensitive::Case));
+    assert_eq!("CASE".parse::<CaseSensitive>(), Ok(CaseSensitive::Upper));
+    assert_eq!("Other".parse::<CaseSensitive>(), Ok(CaseSensitive::Other));
+    assert!("case".parse::<CaseSensitive>().is_err());
+    assert!("other".parse::<CaseSensitive>().is_err());
+}
+
+#[test]
+fn enum_error() {
+    let err = "turbo".parse::<Mode>().unwrap_err();
+    assert_eq!(err, ModeParseError);
+    assert_eq!(err.expected(), &["Fast", "Safe", "slow-and-steady"]);
+    assert_eq!(
+        err.to_string(),
+        "Invalid `Mode`, expected one of: `Fast`, `Safe`, `slow-and-steady`",
+    );
+    assert!("".parse::<Empty>().is_err());
+}
+
+#[derive(FromStr, Debug, Eq, PartialEq)]
+#[from_str(pattern = "{x},{y}")]
+struct Point2D {
+    x: i32,
+    y: i32,
+}
+
+#[derive(FromStr, Debug, Eq, PartialEq)]
+#[from_str(pattern = "{{{_1} <- {_0}}}")]
+struct Swapped(u8, String);
+
+#[derive(FromStr, Debug, Eq, PartialEq)]
+#[from_str(pattern = "{}x{}")]
+struct Size<T>(T, T);
+
+#[test]
+fn pattern() {
+    assert_eq!("1,2".parse::<Point2D>(), Ok(Point2D { x: 1, y: 2 }));
+    assert_eq!("-1,22".parse::<Point2D>(), Ok(Point2D { x: -1, y: 22 }));
+    assert_eq!(
+        "{a <- 7}".parse::<Swapped>(),
+        Ok(Swapped(7, "a".to_owned())),
+    );
+    assert_eq!("16x9".parse::<Size<u16>>(), Ok(Size(16, 9)));
+}
+
+#[test]
+fn pattern_error() {
+    let err = "1;2".parse::<Point2D>().unwrap_err();
+    assert_eq!(err.field(), None);
+    assert_eq!(
+        err.to_string(),
+        "Invalid `Point2D`, expected pattern `{x},{y}`"
+    );
+
+    let err = "1,two".parse::<Point2D>().unwrap_err();
+    assert_eq!(err.field(), Some("y"));
+    assert_eq!(err.to_string(), "Invalid `Point2D`, cannot parse field `y`");
+
+    assert_eq!("{a <- 7".parse::<Swapped>().unwrap_err().field(), None,);
+    assert_eq!("{a <- 7}}".parse::<Swapped>().unwrap_err().field(), None,);
+    assert_eq!(
+        "16x9x".parse::<Size<u16>>().unwrap_err().field(),
+        Some("_1")
+    );
+}
@@ -55,3 +55,45 @@ pub fn test_is_variant() {
     assert!(Maybe::<()>::Nothing.is_nothing());
     assert!(!Maybe::<()>::Nothing.is_just());
 }
+
+#[derive(IsVariant)]
+enum Response {
+    Ok,
+    #[is_variant(rename = "is_http_error")]
+    HTTPError,
+    #[is_variant(rename = "is_{}_failure")]
+    IO,
+}
+
+#[derive(IsVariant)]
+#[is_variant(rename = "{}_variant")]
+enum Templated {
+    First,
+    #[is_variant(rename = "is_second")]
+    Second,
+}
+
+#[test]
+pub fn test_is_variant_rename() {
+    assert!(Response::HTTPError.is_http_error());
+    assert!(!Response::HTTPError.is_ok());
+    assert!(Response::IO.is_io_failure());
+
+    assert!(Templated::First.first_variant());
+    assert!(Templated::Second.is_second());
+}
+
+mod private {
+    #[derive(IsVariant)]
+    #[is_variant(vis = "pub(crate)")]
+    pub(crate) enum Crate {
+        First,
+        Second,
+    }
+}
+
+#[test]
+pub fn test_is_variant_vis() {
+    assert!(private::Crate::First.is_first());
+    assert!(!private::Crate::Second.is_first());
+}
@@ -167,6 +167,15 @@ macro_rules! use_dollar_crate {
 
 use_dollar_crate!();
 
+#[derive(Debug, Eq, PartialEq)]
+#[derive(Display, FromStr)]
+#[display(fmt = "{x}:{y}")]
+#[from_str(pattern = "{x}:{y}")]
+struct Ratio {
+    x: u32,
+    y: u32,
+}
+
 #[test]
 fn main() {
     let mut myint: MyInt = 5.into();
@@ -273,3 +282,10 @@ fn main() {
     *boxed = 7;
     assert_eq!(MyBoxedInt(Box::new(7)), boxed)
 }
+
+#[test]
+fn display_and_from_str_pattern_are_inverse() {
+    let ratio = Ratio { x: 16, y: 9 };
+    assert_eq!(ratio.to_string(), "16:9");
+    assert_eq!(ratio.to_string().parse(), Ok(ratio));
+}
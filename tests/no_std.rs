@@ -67,8 +67,47 @@ enum MixedInts {
     UnsignedTwo(u32),
 }
 
+#[derive(Debug, TryInto)]
+#[try_into(owned, ref, error = "generated")]
+enum MixedValues {
+    Int(i32),
+    Pair(i32, i32),
+}
+
 #[derive(Not, Add)]
 enum EnumWithUnit {
     SmallInt(i32),
     Unit,
 }
+
+#[derive(FromStr)]
+enum Mode {
+    Fast,
+    #[from_str(rename = "careful")]
+    Safe,
+}
+
+#[derive(Display)]
+#[display(fmt = "<{}>", _0, pad)]
+struct Padded(u32);
+
+#[cfg(feature = "core-error")]
+mod error {
+    #[derive(Debug, Display, Error)]
+    #[display(fmt = "simple error")]
+    struct Simple;
+
+    #[derive(Debug, Display, Error)]
+    #[display(fmt = "wrapped: {}", source)]
+    struct Wrapping {
+        source: Simple,
+    }
+
+    #[derive(Debug, Display, Error)]
+    enum Composite {
+        #[display(fmt = "simple")]
+        Simple(Simple),
+        #[error(transparent)]
+        Wrapping(Wrapping),
+    }
+}
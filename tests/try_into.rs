@@ -197,3 +197,39 @@ fn test_try_into() {
     );
     assert_eq!(Ok(()), i.try_into());
 }
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, TryInto)]
+#[try_into(owned, ref, ref_mut, error = "generated")]
+enum Value<T> {
+    Int(i32),
+    Code(i32),
+    Other(Option<T>),
+    Pair(i32, i32),
+    #[try_into(ignore)]
+    Nothing,
+}
+
+#[test]
+fn test_try_into_generated_error() {
+    let mut v: Value<u8> = Value::Pair(1, 2);
+    assert_eq!(Ok((1, 2)), v.try_into());
+    assert_eq!(Ok((&1, &2)), (&v).try_into());
+    assert_eq!(Ok((&mut 1, &mut 2)), (&mut v).try_into());
+
+    let err = i32::try_from(v).unwrap_err();
+    assert_eq!(err.expected(), &["Int", "Code"]);
+    assert_eq!(err.input(), &Value::Pair(1, 2));
+    assert_eq!(err.into_input(), Value::Pair(1, 2));
+    assert_eq!(err.to_string(), "Only Int, Code can be converted to i32");
+
+    let err = <&Option<u8>>::try_from(&v).unwrap_err();
+    assert_eq!(*err.input(), &Value::Pair(1, 2));
+    assert_eq!(
+        err.to_string(),
+        "Only Other can be converted to Option < T >"
+    );
+
+    let err: Box<dyn std::error::Error> =
+        Box::new(<(i32, i32)>::try_from(Value::<u8>::Nothing).unwrap_err());
+    assert_eq!(err.to_string(), "Only Pair can be converted to (i32, i32)");
+}
@@ -60,3 +60,128 @@ pub fn test_unwrap() {
 pub fn test_unwrap_panic() {
     Maybe::<()>::Nothing.unwrap_just()
 }
+
+#[derive(Debug, PartialEq, Unwrap)]
+#[unwrap(ref, ref_mut)]
+enum Shape {
+    Circle(u32),
+    Rect(u32, u32),
+    Empty,
+}
+
+#[test]
+pub fn test_try_unwrap() {
+    assert_eq!(Maybe::Just(1).try_unwrap_just().ok(), Some(1));
+    assert!(Maybe::<u8>::Nothing.try_unwrap_just().is_err());
+
+    assert_eq!(Shape::Rect(1, 2).try_unwrap_rect(), Ok((1, 2)));
+    assert_eq!(
+        Shape::Rect(1, 2).try_unwrap_circle(),
+        Err(Shape::Rect(1, 2))
+    );
+    assert_eq!(Shape::Empty.try_unwrap_empty(), Ok(()));
+}
+
+#[test]
+pub fn test_expect() {
+    assert_eq!(Maybe::Just(1).expect_just("a value"), 1);
+    assert_eq!(Shape::Circle(3).expect_circle("a circle"), 3);
+}
+
+#[test]
+#[should_panic(expected = "a circle")]
+pub fn test_expect_panic() {
+    Shape::Empty.expect_circle("a circle");
+}
+
+#[test]
+pub fn test_unwrap_ref() {
+    let mut shape = Shape::Rect(1, 2);
+    assert_eq!(shape.unwrap_rect_ref(), (&1, &2));
+    assert_eq!(shape.try_unwrap_circle_ref(), Err(&Shape::Rect(1, 2)));
+    assert_eq!(shape.expect_rect_ref("a rect"), (&1, &2));
+
+    *shape.unwrap_rect_mut().0 = 5;
+    assert_eq!(shape.try_unwrap_rect_mut(), Ok((&mut 5, &mut 2)));
+    *shape.expect_rect_mut("a rect").1 = 7;
+    assert_eq!(shape, Shape::Rect(5, 7));
+    assert!(shape.try_unwrap_empty_mut().is_err());
+}
+
+#[derive(Debug, PartialEq, Unwrap)]
+#[unwrap(ref)]
+enum Event {
+    Click {
+        x: i32,
+        y: i32,
+    },
+    Key {
+        code: u32,
+        repeat: bool,
+    },
+    #[unwrap(fields(text, at))]
+    Paste {
+        at: usize,
+        text: String,
+        len: usize,
+    },
+    #[unwrap(ignore)]
+    Resize {
+        width: u32,
+    },
+}
+
+#[test]
+pub fn test_unwrap_named_fields() {
+    assert_eq!(Event::Click { x: 1, y: 2 }.unwrap_click(), (1, 2));
+    assert_eq!(
+        Event::Key {
+            code: 3,
+            repeat: true
+        }
+        .try_unwrap_key(),
+        Ok((3, true))
+    );
+    let paste = Event::Paste {
+        at: 4,
+        text: "foo".to_string(),
+        len: 3,
+    };
+    assert_eq!(paste.unwrap_paste_ref(), (&"foo".to_string(), &4));
+    assert_eq!(paste.unwrap_paste(), ("foo".to_string(), 4));
+}
+
+#[derive(Unwrap)]
+#[unwrap(ref, rename = "{}_value")]
+enum Value {
+    Int(i32),
+    #[unwrap(rename = "http")]
+    HTTPStatus(u16),
+}
+
+#[test]
+pub fn test_unwrap_rename() {
+    assert_eq!(Value::Int(1).unwrap_int_value(), 1);
+    assert_eq!(Value::Int(1).unwrap_int_value_ref(), &1);
+    assert_eq!(Value::HTTPStatus(404).unwrap_http(), 404);
+    assert_eq!(Value::HTTPStatus(404).try_unwrap_http().ok(), Some(404));
+    assert_eq!(Value::HTTPStatus(404).expect_http_ref("a status"), &404);
+}
+
+mod private {
+    #[derive(Unwrap)]
+    #[unwrap(ref, vis = "")]
+    pub(crate) enum Inherited {
+        Int(i32),
+        Unit,
+    }
+
+    pub(crate) fn unwrap_int(value: &Inherited) -> i32 {
+        *value.unwrap_int_ref()
+    }
+}
+
+#[test]
+pub fn test_unwrap_vis() {
+    assert_eq!(private::unwrap_int(&private::Inherited::Int(1)), 1);
+}